@@ -20,10 +20,9 @@ pub fn cli(py: Python) -> PyResult<()> {
         .expect("Failed starting blocking async runtime")
         .block_on(sideko::cli::cli(args));
 
-    if res.is_err() {
-        std::process::exit(1)
-    } else {
-        std::process::exit(0)
+    match res {
+        Err(e) => std::process::exit(e.exit_code()),
+        Ok(_) => std::process::exit(0),
     }
 }
 
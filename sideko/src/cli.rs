@@ -2,12 +2,12 @@ use std::env;
 
 use crate::{
     cmds,
-    result::CliResult,
-    styles::{self, fmt_cyan},
+    result::{CliError, CliResult, ErrorCode, ErrorFormat},
+    styles::{self, fmt_cyan, ColorChoice},
     utils,
 };
 use camino::Utf8PathBuf;
-use clap::{Parser, Subcommand};
+use clap::{CommandFactory, FromArgMatches, Parser, Subcommand, ValueEnum};
 use log::{error, info, warn};
 use sideko_rest_api::models::CliUpdateSeverityEnum;
 
@@ -43,29 +43,128 @@ pub struct SidekoCli {
         value_parser = crate::utils::validators::validate_file
     )]
     config: Option<Utf8PathBuf>,
+
+    /// use a named configuration profile (e.g. `staging`), defaults to `$SIDEKO_PROFILE` or the `default` profile
+    #[arg(long, global = true)]
+    profile: Option<String>,
+
+    /// skip the update check (equivalent to setting `SIDEKO_NO_UPDATE_CHECK=true`)
+    #[arg(long, global = true)]
+    offline: bool,
+
+    /// proxy url for requests to the sideko api, defaults to `$SIDEKO_PROXY` or `$HTTPS_PROXY`
+    #[arg(long, global = true)]
+    proxy: Option<String>,
+
+    /// comma separated hosts that bypass the proxy, defaults to `$SIDEKO_NO_PROXY`
+    #[arg(long, global = true)]
+    no_proxy: Option<String>,
+
+    /// additional trusted ca certificate (pem or der), repeat for multiple, defaults to `$SIDEKO_CA_CERTS`
+    #[arg(long = "ca-cert", global = true, value_parser = crate::utils::validators::validate_file)]
+    ca_certs: Vec<Utf8PathBuf>,
+
+    /// timeout in seconds for each request to the sideko api, defaults to `$SIDEKO_TIMEOUT`
+    #[arg(long, global = true)]
+    timeout: Option<u64>,
+
+    /// format of the error reported when the command fails, see the exit codes in the readme
+    #[arg(long, global = true, default_value = "text")]
+    error_format: ErrorFormat,
+
+    /// append debug logs of each command as json lines to a file (the api key is redacted), defaults to `$SIDEKO_LOG_FILE`
+    #[arg(long, global = true)]
+    log_file: Option<Utf8PathBuf>,
+
+    /// when to use colors and spinners, `auto` disables them when `$NO_COLOR` is set or the output is not a terminal
+    #[arg(long, global = true, default_value = "auto")]
+    color: ColorChoice,
 }
 impl SidekoCli {
     async fn handle(&self) -> CliResult<()> {
         // init logger and environment
         utils::logging::init_logger(self.quiet, self.verbose);
 
+        if let Some(profile) = &self.profile {
+            let profile = utils::config::Profile::new(profile)?;
+            env::set_var(utils::config::ConfigKey::Profile.to_string(), &profile.name);
+        }
         if let Some(cfg_path) = &self.config {
             env::set_var(utils::config::ConfigKey::ConfigPath.to_string(), cfg_path);
         }
         utils::config::load()?;
+        if let Some(log_file) = &self.log_file {
+            env::set_var(utils::config::ConfigKey::LogFile.to_string(), log_file);
+        }
+        if let Some(log_file) = utils::config::ConfigKey::LogFile
+            .get_env()
+            .filter(|f| !f.is_empty())
+        {
+            // logs are a diagnostic, an unwritable log file must not fail the command
+            if let Err(e) = utils::logging::init_log_file(log_file.as_str().into()) {
+                warn!("logs are not recorded to the log file");
+                e.log();
+            }
+        }
+        if self.uses_manifest() {
+            utils::manifest::load()?;
+        }
+
+        // network flags take priority over the config
+        let network_flags = [
+            (utils::config::ConfigKey::Proxy, self.proxy.clone()),
+            (utils::config::ConfigKey::NoProxy, self.no_proxy.clone()),
+            (
+                utils::config::ConfigKey::CaCerts,
+                (!self.ca_certs.is_empty()).then(|| {
+                    self.ca_certs
+                        .iter()
+                        .map(|p| p.to_string())
+                        .collect::<Vec<_>>()
+                        .join(",")
+                }),
+            ),
+            (
+                utils::config::ConfigKey::Timeout,
+                self.timeout.map(|t| t.to_string()),
+            ),
+        ];
+        for (key, val) in network_flags {
+            if let Some(val) = val {
+                env::set_var(key.to_string(), val);
+            }
+        }
+        if let Err(e) = utils::http::init() {
+            // config commands must keep working to fix broken network settings
+            if matches!(&self.command, SidekoCommands::Config(_)) {
+                warn!("network settings are invalid, requests to sideko will fail");
+                e.log();
+            } else {
+                return Err(e);
+            }
+        }
+
+        if self.offline {
+            env::set_var(utils::config::ConfigKey::NoUpdateCheck.to_string(), "true");
+        }
+        // config commands must keep working to fix the settings of the update check
+        let enforce_updates = !matches!(&self.command, SidekoCommands::Config(_));
+        let update_check = utils::updates::UpdateCheck::start(enforce_updates)?;
 
-        let updates = utils::check_for_updates().await?;
-
-        // Run command
-        let cmd_res = match &self.command {
-            SidekoCommands::Login(cmd) => cmd.handle().await,
-            SidekoCommands::Logout(cmd) => cmd.handle().await,
-            SidekoCommands::Api(cmd) => cmd.handle().await,
-            SidekoCommands::Account(cmd) => cmd.handle().await,
-            SidekoCommands::Sdk(cmd) => cmd.handle().await,
-            SidekoCommands::Doc(cmd) => cmd.handle().await,
-            SidekoCommands::Config(cmd) => cmd.handle().await,
+        // Run command while checking for updates
+        let cmd_future = async {
+            match &self.command {
+                SidekoCommands::Login(cmd) => cmd.handle().await,
+                SidekoCommands::Logout(cmd) => cmd.handle().await,
+                SidekoCommands::Api(cmd) => cmd.handle().await,
+                SidekoCommands::Account(cmd) => cmd.handle().await,
+                SidekoCommands::Whoami(cmd) => cmd.handle().await,
+                SidekoCommands::Sdk(cmd) => cmd.handle().await,
+                SidekoCommands::Doc(cmd) => cmd.handle().await,
+                SidekoCommands::Config(cmd) => cmd.handle().await,
+            }
         };
+        let (cmd_res, updates) = update_check.run(cmd_future).await;
 
         // log update notices
         for update in updates {
@@ -84,6 +183,23 @@ impl SidekoCli {
 
         cmd_res
     }
+
+    /// secret values passed as arguments, redacted from the command recorded in the log file
+    fn secrets(&self) -> Vec<String> {
+        match &self.command {
+            SidekoCommands::Login(cmd) => cmd.key.iter().cloned().collect(),
+            SidekoCommands::Config(cmd) => cmd.secrets(),
+            _ => vec![],
+        }
+    }
+
+    /// whether the command resolves flag defaults from the project manifest
+    fn uses_manifest(&self) -> bool {
+        matches!(
+            &self.command,
+            SidekoCommands::Api(_) | SidekoCommands::Sdk(_) | SidekoCommands::Doc(_)
+        )
+    }
 }
 
 #[derive(Subcommand)]
@@ -100,6 +216,9 @@ enum SidekoCommands {
     #[command(subcommand)]
     Account(cmds::AccountSubcommand),
 
+    /// show who is logged in (alias of `sideko account status`)
+    Whoami(cmds::AccountStatusCommand),
+
     /// generate, customize, and sync sdks
     #[command(subcommand)]
     Sdk(cmds::SdkSubcommand),
@@ -112,6 +231,7 @@ enum SidekoCommands {
     ///
     /// removes the api key from the os-native key service
     /// (e.g. `keychain` on macos, `keyutils` on linux,  or `windows credential manager`)
+    /// and the encrypted credential file
     Logout(cmds::LogoutCommand),
 
     /// configure the cli
@@ -120,13 +240,70 @@ enum SidekoCommands {
 }
 
 pub async fn cli(args: Vec<String>) -> CliResult<()> {
-    let cli = SidekoCli::parse_from(args);
+    // colors are resolved before parsing as the help and usage errors are styled as well
+    let color = color_requested(&args);
+    styles::init_color(&color);
+    let command = SidekoCli::command().color(match color {
+        ColorChoice::Auto => clap::ColorChoice::Auto,
+        ColorChoice::Always => clap::ColorChoice::Always,
+        ColorChoice::Never => clap::ColorChoice::Never,
+    });
+    let parsed = command
+        .try_get_matches_from(&args)
+        .and_then(|mut matches| SidekoCli::from_arg_matches_mut(&mut matches));
+    let cli = match parsed {
+        Ok(cli) => cli,
+        // usage errors are only reported as json when requested, otherwise clap prints
+        // them (and help/version) as usual
+        Err(e) if e.use_stderr() && json_errors_requested(&args) => {
+            let rendered = e.render().to_string();
+            let msg = rendered.lines().next().unwrap_or_default();
+            let err = CliError::general_debug(msg.trim_start_matches("error: "), &rendered)
+                .with_code(ErrorCode::InvalidInput);
+            err.log_json();
+            return Err(err);
+        }
+        Err(e) => e.exit(),
+    };
 
+    utils::logging::start_session(&args, cli.secrets());
     let handled = cli.handle().await;
     if let Err(e) = &handled {
-        e.log();
-        info!("re-run the command in verbose mode (-v/-vv) to for more information")
+        match &cli.error_format {
+            ErrorFormat::Text => {
+                e.log();
+                info!("re-run the command in verbose mode (-v/-vv) to for more information")
+            }
+            ErrorFormat::Json => e.log_json(),
+        }
     }
+    utils::logging::end_session(&handled);
 
     handled
 }
+
+/// value of `--color`, invalid values are reported when the arguments are parsed
+fn color_requested(args: &[String]) -> ColorChoice {
+    // the last occurrence of either form wins, as it does for clap
+    let value = args
+        .iter()
+        .zip(args.iter().skip(1).map(Some).chain([None]))
+        .filter_map(|(arg, next)| match arg.strip_prefix("--color") {
+            Some("") => next.map(String::as_str),
+            Some(rest) => rest.strip_prefix('='),
+            None => None,
+        })
+        .last();
+
+    value
+        .and_then(|val| ColorChoice::from_str(val, true).ok())
+        .unwrap_or_default()
+}
+
+/// whether `--error-format json` was passed, for errors before the arguments are parsed
+fn json_errors_requested(args: &[String]) -> bool {
+    args.iter()
+        .zip(args.iter().skip(1))
+        .any(|(flag, val)| flag == "--error-format" && val == "json")
+        || args.iter().any(|a| a == "--error-format=json")
+}
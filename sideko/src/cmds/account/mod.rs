@@ -1,18 +1,24 @@
 use crate::result::CliResult;
 
 mod my_api_key;
+mod status;
+pub(crate) use status::AccountStatusCommand;
 
 #[derive(clap::Subcommand)]
 pub enum AccountSubcommand {
     // ------------ SUB-COMMANDS ------------
-    /// retrieves value of your personal api key and pastes it to your clipboard
+    /// retrieves value of your personal api key and copies it to your clipboard (or stdout, a file, or the credential store)
     GetMyApiKey(my_api_key::GetMyApiKeyCommand),
+
+    /// shows who is logged in, where the api key came from, and the features of your organization's plan
+    Status(status::AccountStatusCommand),
 }
 
 impl AccountSubcommand {
     pub async fn handle(&self) -> CliResult<()> {
         match self {
             AccountSubcommand::GetMyApiKey(cmd) => cmd.handle().await,
+            AccountSubcommand::Status(cmd) => cmd.handle().await,
         }
     }
 }
@@ -1,27 +1,58 @@
 use arboard::Clipboard;
+use camino::Utf8PathBuf;
 use log::info;
 
 use crate::{
     result::{CliError, CliResult},
     styles::{fmt_green, fmt_yellow},
-    utils::get_sideko_client,
+    utils::{config::ConfigKey, credentials, get_sideko_client, output},
 };
 
 #[derive(clap::Args)]
-pub struct GetMyApiKeyCommand {}
+#[command(group(clap::ArgGroup::new("destination").multiple(false)))]
+pub struct GetMyApiKeyCommand {
+    /// print the api key to stdout instead of copying it to the clipboard
+    #[arg(long, group = "destination")]
+    pub stdout: bool,
+
+    /// write the api key to a file only readable by the current user
+    #[arg(long, group = "destination")]
+    pub output: Option<Utf8PathBuf>,
+
+    /// print a shell statement exporting the api key, e.g. `eval "$(sideko account get-my-api-key --export)"`
+    #[arg(long, group = "destination")]
+    pub export: bool,
+
+    /// save the api key in the credential store of the active profile without displaying it
+    #[arg(long, group = "destination")]
+    pub store: bool,
+}
 
 impl GetMyApiKeyCommand {
     pub async fn handle(&self) -> CliResult<()> {
         let mut client = get_sideko_client();
-        let api_key_res = client.user().me().get_key().await?;
+        let api_key = client.user().me().get_key().await?.api_key;
 
-        let mut clipboard = Clipboard::new()
-            .map_err(|err| CliError::arboard_custom("could not create clipboard", err))?;
-        clipboard.set_text(api_key_res.api_key).map_err(|err| {
-            CliError::arboard_custom("could not copy test to your clipboard", err)
-        })?;
+        if self.stdout {
+            output::print_line(&api_key);
+        } else if self.export {
+            output::print_line(format!("export {}='{api_key}'", ConfigKey::ApiKey));
+        } else if let Some(output) = &self.output {
+            credentials::write_private_file(output, format!("{api_key}\n").as_bytes())?;
+            info!("{} api key written to {output}", fmt_green("✔"));
+        } else if self.store {
+            ConfigKey::ApiKey.set_credential(&api_key)?;
+            info!("{} api key saved to the credential store", fmt_green("✔"));
+            return Ok(());
+        } else {
+            let mut clipboard = Clipboard::new()
+                .map_err(|err| CliError::arboard_custom("could not create clipboard, use --stdout, --output, --export, or --store instead", err))?;
+            clipboard.set_text(api_key).map_err(|err| {
+                CliError::arboard_custom("could not copy test to your clipboard", err)
+            })?;
+            info!("{} api key set to clipboard.", fmt_green("✔"));
+        }
 
-        info!("{} api key set to clipboard.", fmt_green("✔"));
         info!(
             "{} save the key in a secure location.",
             fmt_yellow("⚠️ ⚠️ ⚠️")
@@ -0,0 +1,177 @@
+use crate::{
+    cmds::DisplayOutput,
+    result::{CliError, CliResult, ErrorCode},
+    utils::{
+        self,
+        config::{ConfigKey, Profile},
+        get_sideko_client,
+        template::Template,
+    },
+};
+use sideko_rest_api::models::{Organization, OrganizationFeatures, SdkLanguageEnum, User};
+
+#[derive(clap::Args)]
+pub struct AccountStatusCommand {
+    /// display result format
+    #[arg(long, default_value = "pretty")]
+    pub display: DisplayOutput,
+
+    /// render the result with a go template instead e.g. `{{.user.email}}`
+    #[arg(long, value_parser = Template::parse, conflicts_with = "display")]
+    pub format: Option<Template>,
+}
+
+impl AccountStatusCommand {
+    pub async fn handle(&self) -> CliResult<()> {
+        let profile = Profile::active()?;
+        let Some((_, key_source)) = ConfigKey::ApiKey.resolve()? else {
+            return Err(CliError::general(format!(
+                "not logged in (profile: {profile}), authenticate with `sideko login`"
+            ))
+            .with_code(ErrorCode::Auth));
+        };
+
+        let mut client = get_sideko_client();
+        let user = client.user().me().get().await?;
+        let org = client.org().get().await?;
+
+        let status = AccountStatus {
+            profile: profile.name,
+            base_url: utils::config::get_base_url(),
+            api_key_source: key_source.to_string(),
+            allowed_sdk_languages: allowed_languages(&org.features),
+            user,
+            organization: org,
+        };
+
+        if let Some(template) = &self.format {
+            return template.print(&status);
+        }
+        if self.display.is_structured() {
+            return utils::output::print_data(&self.display, &status);
+        }
+
+        // csv has a single header, so both tables are written as one
+        if matches!(&self.display, DisplayOutput::Csv) {
+            let rows = status
+                .account_rows()
+                .into_iter()
+                .chain(status.feature_rows());
+            return utils::output::print_rows(&self.display, "account", rows);
+        }
+
+        utils::output::print_rows(&self.display, "account", status.account_rows())?;
+        utils::output::print_rows(&self.display, "plan features", status.feature_rows())
+    }
+}
+
+#[derive(serde::Serialize)]
+struct AccountStatus {
+    profile: String,
+    base_url: String,
+    api_key_source: String,
+    allowed_sdk_languages: Vec<SdkLanguageEnum>,
+    user: User,
+    organization: Organization,
+}
+
+impl AccountStatus {
+    fn account_rows(&self) -> Vec<StatusRow> {
+        let user_kind = if self.user.is_service_account {
+            "service account"
+        } else {
+            "user"
+        };
+
+        vec![
+            StatusRow::new(
+                "user",
+                format!("{} {}", self.user.first_name, self.user.last_name),
+            ),
+            StatusRow::new("email", &self.user.email),
+            StatusRow::new("account type", user_kind),
+            StatusRow::new("organization", &self.organization.name),
+            StatusRow::new("subdomain", &self.organization.subdomain),
+            StatusRow::new("profile", &self.profile),
+            StatusRow::new("base url", &self.base_url),
+            StatusRow::new("api key source", &self.api_key_source),
+        ]
+    }
+
+    fn feature_rows(&self) -> Vec<StatusRow> {
+        let features = &self.organization.features;
+        let plan = if features.is_free { "free" } else { "paid" };
+
+        let mut rows = vec![StatusRow::new("plan", plan)];
+        for lang in all_languages() {
+            rows.push(StatusRow::new(
+                format!("{lang} sdk"),
+                display_allowed(language_allowed(features, &lang)),
+            ));
+        }
+        rows.extend([
+            StatusRow::new("sdk tests", display_allowed(features.allow_sdk_tests)),
+            StatusRow::new("sdk cli", display_allowed(features.allow_sdk_cli)),
+            StatusRow::new("max sdk api methods", features.max_sdk_api_methods),
+            StatusRow::new("max api projects", features.max_api_projects),
+            StatusRow::new("max doc projects", features.max_doc_projects),
+            StatusRow::new("max mock servers", features.max_mock_servers),
+            StatusRow::new("max teammates", features.max_teammates),
+            StatusRow::new("max service accounts", features.max_service_accounts),
+        ]);
+
+        rows
+    }
+}
+
+#[derive(tabled::Tabled)]
+struct StatusRow {
+    field: String,
+    value: String,
+}
+
+impl StatusRow {
+    fn new<F: ToString, V: ToString>(field: F, value: V) -> Self {
+        Self {
+            field: field.to_string(),
+            value: value.to_string(),
+        }
+    }
+}
+
+fn all_languages() -> Vec<SdkLanguageEnum> {
+    vec![
+        SdkLanguageEnum::Python,
+        SdkLanguageEnum::Typescript,
+        SdkLanguageEnum::Go,
+        SdkLanguageEnum::Csharp,
+        SdkLanguageEnum::Rust,
+        SdkLanguageEnum::Java,
+    ]
+}
+
+fn language_allowed(features: &OrganizationFeatures, lang: &SdkLanguageEnum) -> bool {
+    match lang {
+        SdkLanguageEnum::Go => features.allow_sdk_go,
+        SdkLanguageEnum::Java => features.allow_sdk_java,
+        SdkLanguageEnum::Python => features.allow_sdk_python,
+        SdkLanguageEnum::Rust => features.allow_sdk_rust,
+        SdkLanguageEnum::Typescript => features.allow_sdk_typescript,
+        SdkLanguageEnum::Csharp => features.allow_sdk_csharp,
+    }
+}
+
+fn allowed_languages(features: &OrganizationFeatures) -> Vec<SdkLanguageEnum> {
+    all_languages()
+        .into_iter()
+        .filter(|l| language_allowed(features, l))
+        .collect()
+}
+
+fn display_allowed(allowed: bool) -> &'static str {
+    if allowed {
+        "✔"
+    } else {
+        "✘ (requires upgrade)"
+    }
+}
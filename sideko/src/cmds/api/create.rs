@@ -1,31 +1,30 @@
 use camino::Utf8PathBuf;
 use sideko_rest_api::{models::VersionOrBump, resources::api::InitRequest, UploadFile};
-use tabled::settings::{object::Rows, Color};
 
 use crate::{
     cmds::DisplayOutput,
     result::{CliError, CliResult},
-    utils::{self, get_sideko_client},
+    utils::{self, get_sideko_client, manifest, template::Template},
 };
 
 use super::{tabled::TabledApi, version::tabled::TabledApiSpec};
 
 #[derive(clap::Args)]
 pub struct ApiCreateCommand {
-    /// name of api (only alphanumeric characters and dashes, e.g. `my-api`)
+    /// name of api (only alphanumeric characters and dashes, e.g. `my-api`) [default: `api.name` of sideko.toml]
     #[arg(long)]
-    pub name: String,
+    pub name: Option<String>,
 
     /// semantic version of initial version (e.g. `0.1.0`)
     #[arg(long, default_value = "0.1.0")]
     pub version: String,
 
-    /// path to openapi spec of initial version (yaml or json format)
+    /// path to openapi spec of initial version (yaml or json format) [default: `api.spec` of sideko.toml]
     #[arg(
         long,
         value_parser = crate::utils::validators::validate_file_json_yaml,
     )]
-    pub spec: Utf8PathBuf,
+    pub spec: Option<Utf8PathBuf>,
 
     /// Allow linting errors to be present in the provided spec [default: false]
     ///
@@ -38,23 +37,26 @@ pub struct ApiCreateCommand {
     #[arg(long)]
     pub disable_mock: bool,
 
-    /// display result as a raw json or prettified
+    /// display result format
     #[arg(long, default_value = "pretty")]
     pub display: DisplayOutput,
+
+    /// render the result with a go template instead e.g. `{{.api.name}} {{.version}}`
+    #[arg(long, value_parser = Template::parse, conflicts_with = "display")]
+    pub format: Option<Template>,
 }
 impl ApiCreateCommand {
     pub async fn handle(&self) -> CliResult<()> {
+        let name = manifest::api_name(self.name.as_ref())?;
+        let spec = manifest::api_spec(self.spec.as_ref())?;
         let mut client = get_sideko_client();
         let version = client
             .api()
             .init(InitRequest {
-                name: self.name.clone(),
+                name,
                 mock_server_enabled: Some(!self.disable_mock),
-                openapi: UploadFile::from_path(self.spec.as_str()).map_err(|e| {
-                    CliError::io_custom(
-                        format!("failed reading openapi from path: {}", &self.spec),
-                        e,
-                    )
+                openapi: UploadFile::from_path(spec.as_str()).map_err(|e| {
+                    CliError::io_custom(format!("failed reading openapi from path: {spec}"), e)
                 })?,
                 version: Some(VersionOrBump::Str(self.version.clone())),
                 allow_lint_errors: Some(self.allow_lint_errors),
@@ -62,28 +64,26 @@ impl ApiCreateCommand {
             })
             .await?;
 
-        match &self.display {
-            DisplayOutput::Raw => utils::logging::log_json_raw(&version),
-            DisplayOutput::Pretty => {
-                let org = client.org().get().await?;
-                let mut api_table = tabled::Table::new([TabledApi {
-                    api: version.api.clone(),
-                    subdomain: org.subdomain.clone(),
-                }]);
-                utils::tabled::header_panel(&mut api_table, "api");
-                api_table.modify(Rows::single(1), Color::BOLD);
-                utils::logging::log_table(api_table);
-
-                let mut version_table = tabled::Table::new([TabledApiSpec {
-                    version,
-                    org_subdomain: org.subdomain.clone(),
-                }]);
-                utils::tabled::header_panel(&mut version_table, "initial version");
-                version_table.modify(Rows::single(1), Color::BOLD);
-                utils::logging::log_table(version_table);
-            }
+        if let Some(template) = &self.format {
+            return template.print(&version);
+        }
+        if self.display.is_structured() {
+            return utils::output::print_data(&self.display, &version);
         }
 
-        Ok(())
+        let org = client.org().get().await?;
+        // the csv of the initial version already includes the api name
+        if !matches!(&self.display, DisplayOutput::Csv) {
+            let api = TabledApi {
+                api: version.api.clone(),
+                subdomain: org.subdomain.clone(),
+            };
+            utils::output::print_rows(&self.display, "api", [api])?;
+        }
+        let version = TabledApiSpec {
+            version,
+            org_subdomain: org.subdomain.clone(),
+        };
+        utils::output::print_rows(&self.display, "initial version", [version])
     }
 }
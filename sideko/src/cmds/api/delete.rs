@@ -0,0 +1,110 @@
+use inquire::Confirm;
+use log::info;
+use sideko_rest_api::resources::{
+    api::{spec::ListRequest, DeleteRequest, GetRequest},
+    sdk,
+};
+
+use crate::{
+    result::{CliError, CliResult, ErrorCode},
+    styles::{fmt_green, fmt_yellow},
+    utils::{archive::ArchivedVersions, get_sideko_client, manifest},
+};
+
+#[derive(clap::Args)]
+pub struct ApiDeleteCommand {
+    /// api name or id e.g. my-api [default: `api.name` of sideko.toml]
+    #[arg(long)]
+    pub name: Option<String>,
+
+    /// delete without confirmation, required when not running interactively
+    #[arg(long)]
+    pub yes: bool,
+}
+
+impl ApiDeleteCommand {
+    pub async fn handle(&self) -> CliResult<()> {
+        let name = manifest::api_name(self.name.as_ref())?;
+        let mut client = get_sideko_client();
+        let api = client
+            .api()
+            .get(GetRequest {
+                api_name: name.clone(),
+            })
+            .await?;
+
+        if !self.yes {
+            let versions = client
+                .api()
+                .spec()
+                .list(ListRequest {
+                    api_name: api.id.clone(),
+                })
+                .await?;
+            let sdks = client
+                .sdk()
+                .list(sdk::ListRequest {
+                    api_name: Some(api.id.clone()),
+                    ..Default::default()
+                })
+                .await?;
+
+            let mut dependents: Vec<String> = versions
+                .iter()
+                .map(|v| format!("api version {}", &v.version))
+                .collect();
+            dependents.extend(
+                sdks.iter()
+                    .map(|s| format!("{} sdk {} v{}", &s.language, &s.name, &s.version)),
+            );
+            confirm_deletion(&format!("api `{}`", &api.name), &dependents)?;
+        }
+
+        client
+            .api()
+            .delete(DeleteRequest {
+                api_name: api.id.clone(),
+            })
+            .await?;
+
+        let mut archived = ArchivedVersions::load();
+        if archived.remove_api(&api.name) {
+            archived.save()?;
+        }
+
+        info!("{} deleted api `{}`", fmt_green("✔"), &api.name);
+        Ok(())
+    }
+}
+
+/// lists what depends on the target and asks for confirmation, returns an Err if the
+/// deletion was declined
+pub(super) fn confirm_deletion(target: &str, dependents: &[String]) -> CliResult<()> {
+    if dependents.is_empty() {
+        info!("nothing depends on {target}");
+    } else {
+        info!("{} depending on {target}:", fmt_yellow("⚠"));
+        for dependent in dependents {
+            info!("  • {dependent}");
+        }
+    }
+
+    let confirmed = Confirm::new(&format!("permanently delete {target}?"))
+        .with_default(false)
+        .with_help_message("pass --yes to delete without confirmation")
+        .prompt()
+        .map_err(|e| {
+            CliError::inquire_custom(
+                format!("failed confirming deletion of {target}, pass --yes to delete without confirmation"),
+                e,
+            )
+        })?;
+    if !confirmed {
+        return Err(CliError::general(format!(
+            "deletion of {target} declined, nothing was deleted"
+        ))
+        .with_code(ErrorCode::Cancelled));
+    }
+
+    Ok(())
+}
@@ -1,11 +1,13 @@
 use crate::{
     cmds::DisplayOutput,
-    result::{CliError, CliResult},
-    utils::{self, get_sideko_client},
+    result::{CliError, CliResult, ErrorCode},
+    utils::{
+        self, get_sideko_client, github, junit, manifest, sarif::SarifLog, template::Template,
+    },
 };
 use camino::Utf8PathBuf;
 use sideko_rest_api::{
-    models::{ApiVersion, LintSeverityEnum},
+    models::{ApiVersion, LintReport, LintSeverityEnum},
     resources::lint::RunRequest,
     UploadFile,
 };
@@ -15,14 +17,14 @@ use super::tabled::TabledLintResult;
 
 #[derive(clap::Args, Debug)]
 pub struct LintCommand {
-    /// Path to local OpenAPI file to lint
+    /// Path to local OpenAPI file to lint [default: `api.spec` of sideko.toml]
     #[arg(
         long,
         value_parser = crate::utils::validators::validate_file_json_yaml,
     )]
     pub spec: Option<Utf8PathBuf>,
 
-    /// API name or id e.g. my-api
+    /// API name or id e.g. my-api [default: `api.name` of sideko.toml]
     #[arg(long)]
     pub name: Option<String>,
 
@@ -34,20 +36,78 @@ pub struct LintCommand {
     #[arg(long)]
     pub errors: bool,
 
-    /// display result as a raw json or prettified
+    /// display result format
     #[arg(long, default_value = "pretty")]
-    pub display: DisplayOutput,
+    pub display: LintDisplay,
 
-    /// save result as a CSV
+    /// render each lint result with a go template instead e.g. `{{.rule}}: {{.message}}`
+    #[arg(long, value_parser = Template::parse, conflicts_with = "display")]
+    pub format: Option<Template>,
+
+    /// save results as a CSV
     #[arg(long)]
     pub save: bool,
+
+    /// also write results to a SARIF file, e.g. for github code scanning
+    #[arg(long, value_name = "PATH")]
+    pub sarif: Option<Utf8PathBuf>,
+
+    /// also write results to a JUnit XML file, e.g. for ci test dashboards
+    #[arg(long, value_name = "PATH")]
+    pub junit: Option<Utf8PathBuf>,
+}
+
+/// display formats of `api lint`, the common formats plus report formats for ci
+#[derive(clap::ValueEnum, Default, Debug, Clone)]
+pub enum LintDisplay {
+    /// pretty printed json
+    Raw,
+    /// tables for the terminal
+    #[default]
+    Pretty,
+    Yaml,
+    /// one json object per line
+    Jsonl,
+    Csv,
+    /// markdown tables, e.g. for pull request descriptions
+    Markdown,
+    /// sarif 2.1.0 log, e.g. for github code scanning or ides
+    Sarif,
+    /// junit xml with a test case per rule, failed by errors
+    Junit,
+    /// github actions workflow commands annotating the spec file
+    Github,
+}
+
+impl LintDisplay {
+    /// the common display format, none for report formats
+    fn common(&self) -> Option<DisplayOutput> {
+        match self {
+            LintDisplay::Raw => Some(DisplayOutput::Raw),
+            LintDisplay::Pretty => Some(DisplayOutput::Pretty),
+            LintDisplay::Yaml => Some(DisplayOutput::Yaml),
+            LintDisplay::Jsonl => Some(DisplayOutput::Jsonl),
+            LintDisplay::Csv => Some(DisplayOutput::Csv),
+            LintDisplay::Markdown => Some(DisplayOutput::Markdown),
+            LintDisplay::Sarif | LintDisplay::Junit | LintDisplay::Github => None,
+        }
+    }
 }
 
 impl LintCommand {
     pub async fn handle(&self) -> CliResult<()> {
+        // explicit flags win, otherwise fall back to the spec or api name of the manifest
+        let (spec, name) = match (&self.spec, &self.name) {
+            (None, None) => match manifest::api_spec_opt(None)? {
+                Some(spec) => (Some(spec), None),
+                None => (None, manifest::api_name(None).ok()),
+            },
+            (spec, name) => (spec.clone(), name.clone()),
+        };
+
         let mut client = get_sideko_client();
 
-        let mut report = match (&self.spec, &self.name, &self.version) {
+        let mut report = match (&spec, &name, &self.version) {
             (Some(spec_path), ..) => {
                 let openapi = UploadFile::from_path(spec_path.as_str()).map_err(|e| {
                     CliError::io_custom(format!("failed reading openapi from path: {spec_path}"), e)
@@ -74,7 +134,8 @@ impl LintCommand {
             _ => {
                 return Err(CliError::general(
                     "you must either provide --spec <PATH> or --name <NAME> --version <VERSION>",
-                ))
+                )
+                .with_code(ErrorCode::InvalidInput))
             }
         };
         if self.errors {
@@ -83,157 +144,209 @@ impl LintCommand {
                 .retain(|result| matches!(&result.severity, LintSeverityEnum::Error));
         }
 
-        match &self.display {
-            DisplayOutput::Raw => utils::logging::log_json_raw(&report),
-            DisplayOutput::Pretty => {
-                let filename =
-                    if let Some(Some(filename)) = self.spec.as_ref().map(|p| p.file_name()) {
-                        filename.to_string()
-                    } else {
-                        format!(
-                            "{name}-{version}-openapi",
-                            name = self.name.clone().unwrap_or_default(),
-                            version = self.version.clone().unwrap_or_default()
-                        )
-                    };
-
-                // build summary table
-                let mut summary_rows: Vec<SummaryRow> = vec![];
-                for result in &report.results {
-                    if let Some(row) = summary_rows
-                        .iter_mut()
-                        .find(|r| r.category == result.category)
-                    {
-                        match &result.severity {
-                            LintSeverityEnum::Error => row.errors += 1,
-                            LintSeverityEnum::Warn => row.warnings += 1,
-                            LintSeverityEnum::Info => row.info += 1,
-                            LintSeverityEnum::Unknown => continue,
-                        }
-                    } else {
-                        let new_row = match &result.severity {
-                            LintSeverityEnum::Error => SummaryRow {
-                                category: result.category.clone(),
-                                errors: 1,
-                                ..Default::default()
-                            },
-                            LintSeverityEnum::Warn => SummaryRow {
-                                category: result.category.clone(),
-                                warnings: 1,
-                                ..Default::default()
-                            },
-                            LintSeverityEnum::Info => SummaryRow {
-                                category: result.category.clone(),
-                                info: 1,
-                                ..Default::default()
-                            },
-                            LintSeverityEnum::Unknown => continue,
-                        };
-                        summary_rows.push(new_row);
-                    }
-                }
-                summary_rows.push(SummaryRow {
-                    category: "Total".into(),
-                    errors: report.summary.errors as usize,
-                    warnings: report.summary.warns as usize,
-                    info: report.summary.infos as usize,
-                });
-                let mut summary_table = tabled::Table::new(summary_rows);
-                utils::tabled::header_panel(
-                    &mut summary_table,
-                    &format!("{filename} Lint Summary"),
-                );
-                summary_table
-                    .modify(Rows::single(1), Color::BOLD)
-                    .modify(Rows::last(), Color::BOLD)
-                    .with(Modify::new(ByContent::new("Errors")).with(Color::FG_RED))
-                    .with(Modify::new(ByContent::new("Warnings")).with(Color::FG_YELLOW))
-                    .with(Modify::new(ByContent::new("Info")).with(Color::FG_BLUE));
-
-                if !&report.results.is_empty() {
-                    let mut report_table =
-                        tabled::Table::new(report.results.clone().into_iter().map(|result| {
-                            Some(TabledLintResult {
-                                filename: filename.to_string(),
-                                result,
-                            })
-                        }));
+        let filename = if let Some(Some(filename)) = spec.as_ref().map(|p| p.file_name()) {
+            filename.to_string()
+        } else {
+            format!(
+                "{name}-{version}-openapi",
+                name = name.clone().unwrap_or_default(),
+                version = self.version.clone().unwrap_or_default()
+            )
+        };
+        // reports locate results in the linted file, relative to the working directory
+        let report_uri = spec
+            .as_ref()
+            .map(|p| p.to_string())
+            .unwrap_or_else(|| filename.clone());
+        let result_rows = || {
+            report.results.iter().map(|result| TabledLintResult {
+                filename: filename.clone(),
+                result: result.clone(),
+            })
+        };
+
+        if let Some(template) = &self.format {
+            template.print(&report.results)?;
+        } else if let LintDisplay::Sarif = &self.display {
+            utils::output::print_json_raw(&SarifLog::new(&report_uri, &report.results));
+        } else if let LintDisplay::Junit = &self.display {
+            utils::output::print_line(junit::lint_report(&report_uri, &report.results).trim_end());
+        } else if let LintDisplay::Github = &self.display {
+            for result in &report.results {
+                utils::output::print_line(github::lint_annotation(&report_uri, result));
+            }
+        } else if let Some(display) = self.display.common() {
+            match &display {
+                display if display.is_structured() => utils::output::print_data(display, &report)?,
+                DisplayOutput::Pretty => {
+                    let mut summary_table = tabled::Table::new(summary_rows(&report));
                     utils::tabled::header_panel(
-                        &mut report_table,
-                        &format!("{filename} Lint Results"),
+                        &mut summary_table,
+                        &format!("{filename} Lint Summary"),
                     );
-                    report_table
-                        .with(Modify::new(ByContent::new("error")).with(Color::FG_RED))
-                        .with(Modify::new(ByContent::new("warn")).with(Color::FG_YELLOW))
-                        .with(Modify::new(ByContent::new("info")).with(Color::FG_BLUE));
-                    report_table.modify(Rows::single(1), Color::BOLD);
+                    summary_table
+                        .modify(Rows::single(1), Color::BOLD)
+                        .modify(Rows::last(), Color::BOLD)
+                        .with(Modify::new(ByContent::new("Errors")).with(Color::FG_RED))
+                        .with(Modify::new(ByContent::new("Warnings")).with(Color::FG_YELLOW))
+                        .with(Modify::new(ByContent::new("Info")).with(Color::FG_BLUE));
 
-                    utils::logging::log_table(report_table);
-                }
+                    if !&report.results.is_empty() {
+                        let mut report_table = tabled::Table::new(result_rows());
+                        utils::tabled::header_panel(
+                            &mut report_table,
+                            &format!("{filename} Lint Results"),
+                        );
+                        report_table
+                            .with(Modify::new(ByContent::new("error")).with(Color::FG_RED))
+                            .with(Modify::new(ByContent::new("warn")).with(Color::FG_YELLOW))
+                            .with(Modify::new(ByContent::new("info")).with(Color::FG_BLUE));
+                        report_table.modify(Rows::single(1), Color::BOLD);
+
+                        utils::output::print_table(report_table);
+                    }
 
-                // display summary table
-                utils::logging::log_table(summary_table);
+                    // display summary table
+                    utils::output::print_table(summary_table);
+                }
+                // csv has a single header, the summary can be derived from the results
+                DisplayOutput::Csv => utils::output::print_rows(&display, "", result_rows())?,
+                display => {
+                    if !report.results.is_empty() {
+                        utils::output::print_rows(
+                            display,
+                            &format!("{filename} Lint Results"),
+                            result_rows(),
+                        )?;
+                    }
+                    utils::output::print_rows(
+                        display,
+                        &format!("{filename} Lint Summary"),
+                        summary_rows(&report),
+                    )?;
+                }
             }
         }
 
         if self.save {
-            let filename = if let Some(Some(filename)) = self.spec.as_ref().map(|p| p.file_name()) {
+            let filename = if let Some(Some(filename)) = spec.as_ref().map(|p| p.file_name()) {
                 filename.to_string()
             } else {
                 format!(
                     "{name}-lint-report",
-                    name = self.name.clone().unwrap_or_default(),
+                    name = name.clone().unwrap_or_default(),
                 )
             };
             let csv_filename = format!("{}.csv", filename);
             let file = std::fs::File::create(&csv_filename)
                 .map_err(|e| CliError::io_custom(format!("Failed to create CSV file: {}", e), e))?;
-            let mut wtr = csv::WriterBuilder::new().from_writer(file);
-            #[derive(serde::Serialize)]
-            struct FlatLintResult {
-                category: String,
-                severity: String,
-                message: String,
-                path: String,
-                start_line: i64,
-                start_column: i64,
-                end_line: i64,
-                end_column: i64,
-            }
-            for result in &report.results {
-                let location = result.location.clone();
-                let flat_result = FlatLintResult {
-                    category: result.category.clone(),
-                    severity: result.severity.to_string(),
-                    message: result.message.clone(),
-                    path: location.path.clone(),
-                    start_line: location.start_line,
-                    start_column: location.start_column,
-                    end_line: location.end_line,
-                    end_column: location.end_column,
-                };
-                wtr.serialize(flat_result).map_err(|e| {
-                    CliError::io_custom(format!("Failed to write CSV data: {}", e), e.into())
-                })?;
-            }
-            wtr.flush()
-                .map_err(|e| CliError::io_custom(format!("Failed to flush CSV data: {}", e), e))?;
+            // the saved report keeps its own columns, scripts read them
+            let headers = [
+                "category",
+                "severity",
+                "message",
+                "path",
+                "start_line",
+                "start_column",
+                "end_line",
+                "end_column",
+            ]
+            .map(String::from);
+            let records: Vec<Vec<String>> = report
+                .results
+                .iter()
+                .map(|result| {
+                    let location = &result.location;
+                    vec![
+                        result.category.clone(),
+                        result.severity.to_string(),
+                        result.message.clone(),
+                        location.path.clone(),
+                        location.start_line.to_string(),
+                        location.start_column.to_string(),
+                        location.end_line.to_string(),
+                        location.end_column.to_string(),
+                    ]
+                })
+                .collect();
+            utils::output::write_csv(file, &headers, &records)?;
 
             // Inform the user where the report was saved
             log::info!("Lint report saved to: {}", csv_filename);
         }
 
+        if let Some(path) = &self.sarif {
+            let sarif = serde_json::to_string_pretty(&SarifLog::new(&report_uri, &report.results))
+                .map_err(|e| CliError::general_debug("failed serializing SARIF report", e))?;
+            std::fs::write(path, sarif).map_err(|e| {
+                CliError::io_custom(format!("failed writing SARIF report: {path}"), e)
+            })?;
+            log::info!("SARIF report saved to: {path}");
+        }
+        if let Some(path) = &self.junit {
+            std::fs::write(path, junit::lint_report(&report_uri, &report.results)).map_err(
+                |e| CliError::io_custom(format!("failed writing JUnit report: {path}"), e),
+            )?;
+            log::info!("JUnit report saved to: {path}");
+        }
+
         if report.summary.errors > 0 {
-            Err(CliError::general(format!(
-                "{} linting errors found",
-                report.summary.errors
-            )))
+            Err(
+                CliError::general(format!("{} linting errors found", report.summary.errors))
+                    .with_code(ErrorCode::LintErrors),
+            )
         } else {
             Ok(())
         }
     }
 }
 
+/// counts of results by category, followed by the total
+fn summary_rows(report: &LintReport) -> Vec<SummaryRow> {
+    let mut summary_rows: Vec<SummaryRow> = vec![];
+    for result in &report.results {
+        if let Some(row) = summary_rows
+            .iter_mut()
+            .find(|r| r.category == result.category)
+        {
+            match &result.severity {
+                LintSeverityEnum::Error => row.errors += 1,
+                LintSeverityEnum::Warn => row.warnings += 1,
+                LintSeverityEnum::Info => row.info += 1,
+                LintSeverityEnum::Unknown => continue,
+            }
+        } else {
+            let new_row = match &result.severity {
+                LintSeverityEnum::Error => SummaryRow {
+                    category: result.category.clone(),
+                    errors: 1,
+                    ..Default::default()
+                },
+                LintSeverityEnum::Warn => SummaryRow {
+                    category: result.category.clone(),
+                    warnings: 1,
+                    ..Default::default()
+                },
+                LintSeverityEnum::Info => SummaryRow {
+                    category: result.category.clone(),
+                    info: 1,
+                    ..Default::default()
+                },
+                LintSeverityEnum::Unknown => continue,
+            };
+            summary_rows.push(new_row);
+        }
+    }
+    summary_rows.push(SummaryRow {
+        category: "Total".into(),
+        errors: report.summary.errors as usize,
+        warnings: report.summary.warns as usize,
+        info: report.summary.infos as usize,
+    });
+
+    summary_rows
+}
+
 #[derive(tabled::Tabled, Default)]
 struct SummaryRow {
     #[tabled(rename = "Category")]
@@ -1,39 +1,41 @@
-use tabled::settings::{object::Rows, Color};
-
 use crate::{
-    cmds::DisplayOutput,
+    cmds::{DisplayOutput, ListOptions},
     result::CliResult,
-    utils::{self, get_sideko_client},
+    utils::{self, get_sideko_client, template::Template},
 };
 
 use super::tabled::TabledApi;
 
 #[derive(clap::Args)]
 pub struct ApiListCommand {
-    /// display result as a raw json or prettified
+    /// display result format
     #[arg(long, default_value = "pretty")]
     pub display: DisplayOutput,
+
+    /// render each result with a go template instead e.g. `{{.name}}`
+    #[arg(long, value_parser = Template::parse, conflicts_with = "display")]
+    pub format: Option<Template>,
+
+    #[command(flatten)]
+    pub list: ListOptions,
 }
 impl ApiListCommand {
     pub async fn handle(&self) -> CliResult<()> {
         let mut client = get_sideko_client();
         let apis = client.api().list().await?;
 
-        match &self.display {
-            DisplayOutput::Raw => utils::logging::log_json_raw(&apis),
-            DisplayOutput::Pretty => {
-                let org = client.org().get().await?;
-
-                let mut table = tabled::Table::new(apis.into_iter().map(|api| TabledApi {
-                    api,
-                    subdomain: org.subdomain.clone(),
-                }));
-                utils::tabled::header_panel(&mut table, "apis");
-                table.modify(Rows::single(1), Color::BOLD);
-                utils::logging::log_table(table);
-            }
-        }
-
-        Ok(())
+        let org = client.org().get().await?;
+        let rows = apis.into_iter().map(|api| TabledApi {
+            api,
+            subdomain: org.subdomain.clone(),
+        });
+        utils::listing::print_list(
+            &self.display,
+            self.format.as_ref(),
+            "apis",
+            &self.list,
+            rows,
+            |r| &r.api,
+        )
     }
 }
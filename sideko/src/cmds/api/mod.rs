@@ -1,6 +1,7 @@
 use crate::result::CliResult;
 
 mod create;
+mod delete;
 mod lint;
 mod list;
 mod stats;
@@ -17,6 +18,8 @@ pub enum ApiSubcommand {
     // ------------ COMMANDS ------------
     /// create a new api
     Create(create::ApiCreateCommand),
+    /// permanently deletes an api with all of its versions
+    Delete(delete::ApiDeleteCommand),
     /// list all apis
     List(list::ApiListCommand),
     /// display stats gathered from the specification
@@ -30,6 +33,7 @@ impl ApiSubcommand {
         match self {
             ApiSubcommand::Version(cmd) => cmd.handle().await,
             ApiSubcommand::Create(cmd) => cmd.handle().await,
+            ApiSubcommand::Delete(cmd) => cmd.handle().await,
             ApiSubcommand::List(cmd) => cmd.handle().await,
             ApiSubcommand::Stats(cmd) => cmd.handle().await,
             ApiSubcommand::Lint(cmd) => cmd.handle().await,
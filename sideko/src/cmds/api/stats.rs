@@ -4,56 +4,68 @@ use tabled::settings::{object::Rows, Remove};
 use crate::{
     cmds::DisplayOutput,
     result::CliResult,
-    utils::{self, get_sideko_client},
+    utils::{self, get_sideko_client, manifest, template::Template},
 };
 
 #[derive(clap::Args, Debug)]
 pub struct ApiStatsCommand {
-    /// api name or id e.g. my-api
+    /// api name or id e.g. my-api [default: `api.name` of sideko.toml]
     #[arg(long)]
-    pub name: String,
+    pub name: Option<String>,
 
     /// api name or id e.g. my-api
     #[arg(long, default_value = "latest")]
     pub version: String,
 
-    /// display result as a raw json or prettified
+    /// display result format
     #[arg(long, default_value = "pretty")]
     pub display: DisplayOutput,
+
+    /// render the result with a go template instead e.g. `{{.endpoints}}`
+    #[arg(long, value_parser = Template::parse, conflicts_with = "display")]
+    pub format: Option<Template>,
 }
 impl ApiStatsCommand {
     pub async fn handle(&self) -> CliResult<()> {
+        let name = manifest::api_name(self.name.as_ref())?;
         let mut client = get_sideko_client();
         let stats = client
             .api()
             .spec()
             .get_stats(GetStatsRequest {
-                api_name: self.name.clone(),
+                api_name: name,
                 api_version: ApiVersion::Str(self.version.clone()),
             })
             .await?;
 
-        match &self.display {
-            DisplayOutput::Raw => utils::logging::log_json_raw(&stats),
-            DisplayOutput::Pretty => {
-                let auth_schemes = if stats.authentication_schemes.is_empty() {
-                    "None".to_string()
-                } else {
-                    stats.authentication_schemes.join("; ")
-                };
-                let summary_data = vec![
-                    SummaryRow::new("total endpoints (paths)", stats.endpoints),
-                    SummaryRow::new("total methods (operations)", stats.methods),
-                    SummaryRow::new("authenticated methods", stats.authenticated_methods),
-                    SummaryRow::new("public methods", stats.public_methods),
-                    SummaryRow::new("authentication schemes", auth_schemes),
-                ];
-                let mut table = tabled::Table::new(summary_data);
-                table.with(Remove::row(Rows::first()));
-                utils::tabled::header_panel(&mut table, "stats");
+        if let Some(template) = &self.format {
+            return template.print(&stats);
+        }
+        if self.display.is_structured() {
+            return utils::output::print_data(&self.display, &stats);
+        }
+
+        let auth_schemes = if stats.authentication_schemes.is_empty() {
+            "None".to_string()
+        } else {
+            stats.authentication_schemes.join("; ")
+        };
+        let summary_data = vec![
+            SummaryRow::new("total endpoints (paths)", stats.endpoints),
+            SummaryRow::new("total methods (operations)", stats.methods),
+            SummaryRow::new("authenticated methods", stats.authenticated_methods),
+            SummaryRow::new("public methods", stats.public_methods),
+            SummaryRow::new("authentication schemes", auth_schemes),
+        ];
+
+        if matches!(&self.display, DisplayOutput::Pretty) {
+            let mut table = tabled::Table::new(summary_data);
+            table.with(Remove::row(Rows::first()));
+            utils::tabled::header_panel(&mut table, "stats");
 
-                utils::logging::log_table(table);
-            }
+            utils::output::print_table(table);
+        } else {
+            utils::output::print_rows(&self.display, "stats", summary_data)?;
         }
         Ok(())
     }
@@ -0,0 +1,59 @@
+use log::info;
+use sideko_rest_api::{models::ApiVersion, resources::api::spec::GetRequest};
+
+use crate::{
+    result::CliResult,
+    styles::fmt_green,
+    utils::{archive::ArchivedVersions, get_sideko_client, manifest},
+};
+
+#[derive(clap::Args)]
+pub struct ApiVersionArchiveCommand {
+    /// api name or id e.g. my-api [default: `api.name` of sideko.toml]
+    #[arg(long)]
+    pub name: Option<String>,
+
+    /// version to archive or unarchive (e.g. `2.1.5`)
+    #[arg(long)]
+    pub version: String,
+}
+
+impl ApiVersionArchiveCommand {
+    /// archives the version, or restores it if `archive` is false
+    pub async fn handle(&self, archive: bool) -> CliResult<()> {
+        let name = manifest::api_name(self.name.as_ref())?;
+        let mut client = get_sideko_client();
+        let version = client
+            .api()
+            .spec()
+            .get(GetRequest {
+                api_name: name,
+                api_version: ApiVersion::Str(self.version.clone()),
+            })
+            .await?;
+        let target = format!(
+            "version {} of api `{}`",
+            &version.version, &version.api.name
+        );
+
+        let mut archived = ArchivedVersions::load();
+        if !archive {
+            if archived.unarchive(&version) {
+                archived.save()?;
+                info!("{} unarchived {target}", fmt_green("✔"));
+            } else {
+                info!("{target} is not archived");
+            }
+        } else if archived.archive(&version) {
+            archived.save()?;
+            info!(
+                "{} archived {target}, it is hidden from `api version list` unless listed with --archived",
+                fmt_green("✔")
+            );
+        } else {
+            info!("{target} is already archived");
+        }
+
+        Ok(())
+    }
+}
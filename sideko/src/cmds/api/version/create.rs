@@ -1,32 +1,40 @@
 use camino::Utf8PathBuf;
-use sideko_rest_api::{models::VersionOrBump, resources::api::spec::CreateRequest, UploadFile};
-use tabled::settings::{object::Rows, Color};
+use log::info;
+use sideko_rest_api::{
+    models::VersionOrBump,
+    resources::api::spec::{CreateRequest, ListRequest},
+    SidekoClient, UploadFile,
+};
 
 use crate::{
     cmds::DisplayOutput,
-    result::{CliError, CliResult},
-    utils::{self, get_sideko_client},
+    result::{CliError, CliResult, ErrorCode},
+    utils::{
+        self, get_sideko_client, manifest,
+        spec_diff::{self, Bump},
+        template::Template,
+    },
 };
 
-use super::tabled::TabledApiSpec;
+use super::tabled::{TabledApiSpec, TabledVersionProposal};
 
 #[derive(clap::Args)]
 pub struct ApiVersionCreateCommand {
-    /// api name or id e.g. my-api
+    /// api name or id e.g. my-api [default: `api.name` of sideko.toml]
     #[arg(long)]
-    pub name: String,
+    pub name: Option<String>,
 
     /// semantic version (e.g. `2.1.5`) or version bump
     /// (`auto`, `patch`, `minor`, `major`, `rc-patch`, `rc-minor`, `rc-major`, `release`)
     #[arg(long, default_value = "auto")]
     pub version: String,
 
-    /// path to openapi specification (YAML or JSON format)
+    /// path to openapi specification (YAML or JSON format) [default: `api.spec` of sideko.toml]
     #[arg(
         long,
         value_parser = crate::utils::validators::validate_file_json_yaml,
     )]
-    pub spec: Utf8PathBuf,
+    pub spec: Option<Utf8PathBuf>,
 
     /// Allow linting errors to be present in the provided spec [default: false]
     ///
@@ -39,46 +47,273 @@ pub struct ApiVersionCreateCommand {
     #[arg(long)]
     pub disable_mock: bool,
 
-    /// display result as a raw json or prettified
+    /// only show the version `--explain` would create and why, comparing the spec with
+    /// the latest version locally
+    #[arg(long)]
+    pub dry_run: bool,
+
+    /// create the version inferred by comparing the spec with the latest version locally
+    /// and explain why
+    ///
+    /// the inferred version is sent instead of `--version`, so the created version may
+    /// differ from the one sideko would resolve `--version` (e.g. `auto`) to
+    #[arg(long, conflicts_with = "dry_run")]
+    pub explain: bool,
+
+    /// display result format
     #[arg(long, default_value = "pretty")]
     pub display: DisplayOutput,
+
+    /// render the result with a go template instead e.g. `{{.version}}`
+    #[arg(long, value_parser = Template::parse, conflicts_with = "display")]
+    pub format: Option<Template>,
 }
 impl ApiVersionCreateCommand {
     pub async fn handle(&self) -> CliResult<()> {
+        let name = manifest::api_name(self.name.as_ref())?;
+        let spec = manifest::api_spec(self.spec.as_ref())?;
         let mut client = get_sideko_client();
+
+        let proposal = if self.dry_run || self.explain {
+            Some(self.propose(&mut client, &name, &spec).await?)
+        } else {
+            None
+        };
+        if let Some(proposal) = proposal.as_ref().filter(|_| self.dry_run) {
+            if let Some(template) = &self.format {
+                return template.print(proposal);
+            }
+            if self.display.is_structured() {
+                return utils::output::print_data(&self.display, proposal);
+            }
+            proposal.log_reasons();
+            utils::output::print_rows(
+                &self.display,
+                "proposed api version",
+                [TabledVersionProposal {
+                    proposal: proposal.clone(),
+                }],
+            )?;
+            info!("dry run, no version was created");
+            return Ok(());
+        }
+        if let Some(proposal) = &proposal {
+            proposal.log_reasons();
+        }
+        // the proposed version is sent as is, so the created version is the explained one
+        let version = proposal
+            .as_ref()
+            .and_then(|p| p.proposed_version.clone())
+            .unwrap_or_else(|| self.version.clone());
+
         let new_version = client
             .api()
             .spec()
             .create(CreateRequest {
-                api_name: self.name.clone(),
-                version: Some(VersionOrBump::Str(self.version.clone())),
+                api_name: name.clone(),
+                version: Some(VersionOrBump::Str(version)),
                 mock_server_enabled: Some(!self.disable_mock),
-                openapi: UploadFile::from_path(self.spec.as_str()).map_err(|e| {
-                    CliError::io_custom(
-                        format!("failed reading openapi from path: {}", &self.spec),
-                        e,
-                    )
+                openapi: UploadFile::from_path(spec.as_str()).map_err(|e| {
+                    CliError::io_custom(format!("failed reading openapi from path: {spec}"), e)
                 })?,
                 notes: None,
                 allow_lint_errors: Some(self.allow_lint_errors),
             })
             .await?;
 
-        match &self.display {
-            DisplayOutput::Raw => utils::logging::log_json_raw(&new_version),
-            DisplayOutput::Pretty => {
-                let org = client.org().get().await?;
-                let mut table = tabled::Table::new([TabledApiSpec {
-                    version: new_version,
-                    org_subdomain: org.subdomain.clone(),
-                }]);
-                utils::tabled::header_panel(&mut table, "new api version");
-                table.modify(Rows::single(1), Color::BOLD);
-
-                utils::logging::log_table(table);
+        if let Some(template) = &self.format {
+            return template.print(&new_version);
+        }
+        if self.display.is_structured() {
+            return utils::output::print_data(&self.display, &new_version);
+        }
+
+        let org = client.org().get().await?;
+        let row = TabledApiSpec {
+            version: new_version,
+            org_subdomain: org.subdomain.clone(),
+        };
+        utils::output::print_rows(&self.display, "new api version", [row])
+    }
+}
+
+impl ApiVersionCreateCommand {
+    /// infers the version `--version` resolves to from the changes since the latest version
+    async fn propose(
+        &self,
+        client: &mut SidekoClient,
+        name: &str,
+        spec: &Utf8PathBuf,
+    ) -> CliResult<VersionProposal> {
+        let explicit = semver::Version::parse(&self.version).ok();
+        let versions = client
+            .api()
+            .spec()
+            .list(ListRequest {
+                api_name: name.to_string(),
+            })
+            .await?;
+        // versions are compared by precedence, falling back to the most recent one when
+        // none is a semantic version
+        let latest = versions
+            .iter()
+            .filter_map(|v| semver::Version::parse(&v.version).ok().map(|s| (s, v)))
+            .max_by(|(a, _), (b, _)| a.cmp(b))
+            .map(|(_, v)| v)
+            .or_else(|| versions.first());
+        let Some(latest) = latest else {
+            return Ok(VersionProposal {
+                api: name.to_string(),
+                latest_version: None,
+                proposed_version: explicit.map(|v| v.to_string()),
+                bump: None,
+                reasons: vec!["no existing version to compare with".into()],
+                changes: vec![],
+            });
+        };
+
+        let content = std::fs::read_to_string(spec).map_err(|e| {
+            CliError::io_custom(format!("failed reading openapi from path: {spec}"), e)
+        })?;
+        let new_spec = super::diff::parse_spec(&content, spec.as_str())?;
+        let latest_spec =
+            super::diff::load_version(client, Some(&name.to_string()), &latest.version).await?;
+        let changes = spec_diff::diff(&latest_spec, &new_spec);
+        let (inferred, mut reasons) = Bump::infer(&changes);
+
+        let latest_semver = semver::Version::parse(&latest.version).ok();
+        let (bump, proposed) = match (self.version.as_str(), &latest_semver) {
+            (_, _) if explicit.is_some() => {
+                reasons.push(format!("`{}` was requested explicitly", &self.version));
+                (None, explicit)
+            }
+            (_, None) => {
+                reasons.push(format!(
+                    "the latest version `{}` is not a semantic version",
+                    &latest.version
+                ));
+                (None, None)
+            }
+            ("auto", Some(latest)) => (Some(inferred), Some(bump_version(latest, inferred))),
+            ("patch" | "minor" | "major", Some(latest)) => {
+                let bump = match self.version.as_str() {
+                    "patch" => Bump::Patch,
+                    "minor" => Bump::Minor,
+                    _ => Bump::Major,
+                };
+                if bump < inferred {
+                    reasons.push(format!(
+                        "`{}` was requested although the changes call for a {} bump",
+                        &self.version,
+                        inferred.as_str()
+                    ));
+                }
+                (Some(bump), Some(bump_version(latest, bump)))
+            }
+            ("rc-patch" | "rc-minor" | "rc-major", Some(latest)) => {
+                let bump = match self.version.as_str() {
+                    "rc-patch" => Bump::Patch,
+                    "rc-minor" => Bump::Minor,
+                    _ => Bump::Major,
+                };
+                (Some(bump), Some(release_candidate(latest, bump)))
+            }
+            ("release", Some(latest)) if !latest.pre.is_empty() => {
+                let mut release = latest.clone();
+                release.pre = semver::Prerelease::EMPTY;
+                reasons.push(format!("releases the candidate `{latest}`"));
+                (None, Some(release))
             }
+            ("release", Some(latest)) => {
+                reasons.push(format!(
+                    "`release` requires the latest version to be a release candidate, `{latest}` is not"
+                ));
+                (None, None)
+            }
+            (version, _) => {
+                return Err(CliError::general(format!(
+                    "invalid --version `{version}`, expected a semantic version or version bump"
+                ))
+                .with_code(ErrorCode::InvalidInput))
+            }
+        };
+
+        Ok(VersionProposal {
+            api: name.to_string(),
+            latest_version: Some(latest.version.clone()),
+            proposed_version: proposed.map(|v| v.to_string()),
+            bump,
+            reasons,
+            changes,
+        })
+    }
+}
+
+/// the version `api version create` is expected to create and why
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct VersionProposal {
+    pub api: String,
+    pub latest_version: Option<String>,
+    pub proposed_version: Option<String>,
+    pub bump: Option<Bump>,
+    pub reasons: Vec<String>,
+    pub changes: Vec<spec_diff::Change>,
+}
+
+impl VersionProposal {
+    fn log_reasons(&self) {
+        info!(
+            "{} → {}",
+            self.latest_version.as_deref().unwrap_or("none"),
+            self.proposed_version
+                .as_deref()
+                .unwrap_or("chosen by sideko")
+        );
+        for reason in &self.reasons {
+            info!("  • {reason}");
         }
+    }
+}
 
-        Ok(())
+/// next version of the bump level, a pre-release is released by the first bump that
+/// reaches it, e.g. `1.3.0-rc.0` is released as `1.3.0` by a patch or minor bump
+fn bump_version(latest: &semver::Version, bump: Bump) -> semver::Version {
+    let mut version = latest.clone();
+    version.pre = semver::Prerelease::EMPTY;
+    version.build = semver::BuildMetadata::EMPTY;
+    let pre = !latest.pre.is_empty();
+    match bump {
+        Bump::Patch if pre => (),
+        Bump::Patch => version.patch += 1,
+        Bump::Minor if pre && latest.patch == 0 => (),
+        Bump::Minor => {
+            version.minor += 1;
+            version.patch = 0;
+        }
+        Bump::Major if pre && latest.minor == 0 && latest.patch == 0 => (),
+        Bump::Major => {
+            version.major += 1;
+            version.minor = 0;
+            version.patch = 0;
+        }
     }
+
+    version
+}
+
+/// next release candidate, e.g. `1.3.0-rc.0` after `1.2.4` or `1.3.0-rc.1` after `1.3.0-rc.0`
+fn release_candidate(latest: &semver::Version, bump: Bump) -> semver::Version {
+    let rc = latest
+        .pre
+        .as_str()
+        .strip_prefix("rc.")
+        .and_then(|n| n.parse::<u64>().ok());
+    let mut version = match rc {
+        Some(_) => latest.clone(),
+        None => bump_version(latest, bump),
+    };
+    version.pre = semver::Prerelease::new(&format!("rc.{}", rc.map_or(0, |n| n + 1)))
+        .unwrap_or(semver::Prerelease::EMPTY);
+
+    version
 }
@@ -0,0 +1,83 @@
+use log::info;
+use sideko_rest_api::{
+    models::ApiVersion,
+    resources::{
+        api::spec::{DeleteRequest, GetRequest},
+        sdk,
+    },
+};
+
+use crate::{
+    result::CliResult,
+    styles::fmt_green,
+    utils::{archive::ArchivedVersions, get_sideko_client, manifest},
+};
+
+use super::super::delete::confirm_deletion;
+
+#[derive(clap::Args)]
+pub struct ApiVersionDeleteCommand {
+    /// api name or id e.g. my-api [default: `api.name` of sideko.toml]
+    #[arg(long)]
+    pub name: Option<String>,
+
+    /// version to delete (e.g. `2.1.5`)
+    #[arg(long)]
+    pub version: String,
+
+    /// delete without confirmation, required when not running interactively
+    #[arg(long)]
+    pub yes: bool,
+}
+
+impl ApiVersionDeleteCommand {
+    pub async fn handle(&self) -> CliResult<()> {
+        let name = manifest::api_name(self.name.as_ref())?;
+        let mut client = get_sideko_client();
+        let version = client
+            .api()
+            .spec()
+            .get(GetRequest {
+                api_name: name.clone(),
+                api_version: ApiVersion::Str(self.version.clone()),
+            })
+            .await?;
+        let target = format!(
+            "version {} of api `{}`",
+            &version.version, &version.api.name
+        );
+
+        if !self.yes {
+            let sdks = client
+                .sdk()
+                .list(sdk::ListRequest {
+                    api_name: Some(version.api.id.clone()),
+                    api_version: Some(version.id.clone()),
+                    ..Default::default()
+                })
+                .await?;
+            let dependents: Vec<String> = sdks
+                .iter()
+                .map(|s| format!("{} sdk {} v{}", &s.language, &s.name, &s.version))
+                .collect();
+            confirm_deletion(&target, &dependents)?;
+        }
+
+        client
+            .api()
+            .spec()
+            .delete(DeleteRequest {
+                api_name: version.api.id.clone(),
+                api_version: ApiVersion::Str(version.version.clone()),
+            })
+            .await?;
+
+        let mut archived = ArchivedVersions::load();
+        if archived.unarchive(&version) {
+            archived.save()?;
+        }
+
+        info!("{} deleted {target}", fmt_green("✔"));
+        Ok(())
+    }
+}
@@ -0,0 +1,179 @@
+use camino::{Utf8Path, Utf8PathBuf};
+use log::info;
+use sideko_rest_api::{models::ApiVersion, resources::api::spec::GetOpenapiRequest, SidekoClient};
+
+use crate::{
+    cmds::DisplayOutput,
+    result::{CliError, CliResult, ErrorCode},
+    styles::{fmt_green, fmt_red},
+    utils::{self, get_sideko_client, manifest, spec_diff, template::Template},
+};
+
+use super::tabled::TabledSpecChange;
+
+#[derive(clap::Args)]
+pub struct ApiVersionDiffCommand {
+    /// api name or id e.g. my-api [default: `api.name` of sideko.toml]
+    #[arg(long)]
+    pub name: Option<String>,
+
+    /// base version e.g. `1.2.0`
+    #[arg(long, required_unless_present = "from_spec")]
+    pub from: Option<String>,
+
+    /// path to a local openapi spec to use as the base instead of a version
+    #[arg(
+        long,
+        value_parser = crate::utils::validators::validate_file_json_yaml,
+        conflicts_with = "from",
+    )]
+    pub from_spec: Option<Utf8PathBuf>,
+
+    /// version to compare with the base e.g. `2.0.0` or `latest`
+    #[arg(long, default_value = "latest")]
+    pub to: String,
+
+    /// path to a local openapi spec to compare with the base instead of a version
+    #[arg(
+        long,
+        value_parser = crate::utils::validators::validate_file_json_yaml,
+        conflicts_with = "to",
+    )]
+    pub to_spec: Option<Utf8PathBuf>,
+
+    /// exit with a non-zero code if any change is breaking
+    #[arg(long)]
+    pub fail_on_breaking: bool,
+
+    /// display result format
+    #[arg(long, default_value = "pretty")]
+    pub display: DisplayOutput,
+
+    /// render each change with a go template instead e.g. `{{.location}}: {{.detail}}`
+    #[arg(long, value_parser = Template::parse, conflicts_with = "display")]
+    pub format: Option<Template>,
+}
+
+impl ApiVersionDiffCommand {
+    pub async fn handle(&self) -> CliResult<()> {
+        let mut client = get_sideko_client();
+        let from_source = match (&self.from_spec, &self.from) {
+            (Some(path), _) => SpecSource::Path(path),
+            (None, Some(version)) => SpecSource::Version(version),
+            (None, None) => unreachable!("clap requires --from or --from-spec"),
+        };
+        let to_source = match &self.to_spec {
+            Some(path) => SpecSource::Path(path),
+            None => SpecSource::Version(&self.to),
+        };
+        let from = from_source.load(&mut client, self.name.as_ref()).await?;
+        let to = to_source.load(&mut client, self.name.as_ref()).await?;
+
+        let changes = spec_diff::diff(&from, &to);
+        let breaking = changes.iter().filter(|c| c.breaking).count();
+
+        if let Some(template) = &self.format {
+            template.print(&changes)?;
+        } else if self.display.is_structured() {
+            utils::output::print_data(&self.display, &changes)?;
+        } else if changes.is_empty() {
+            info!(
+                "{} no changes from {} to {}",
+                fmt_green("✔"),
+                &from_source,
+                &to_source
+            );
+        } else {
+            utils::output::print_rows(
+                &self.display,
+                &format!("changes from {from_source} to {to_source}"),
+                changes
+                    .iter()
+                    .cloned()
+                    .map(|change| TabledSpecChange { change }),
+            )?;
+            info!(
+                "{} breaking, {} non-breaking changes",
+                if breaking > 0 {
+                    fmt_red(&breaking.to_string())
+                } else {
+                    breaking.to_string()
+                },
+                changes.len() - breaking
+            );
+        }
+
+        if self.fail_on_breaking && breaking > 0 {
+            return Err(CliError::general(format!(
+                "{breaking} breaking changes from {from_source} to {to_source}"
+            ))
+            .with_code(ErrorCode::BreakingChanges));
+        }
+
+        Ok(())
+    }
+}
+
+/// spec compared by the diff, either an api version or a local openapi spec
+enum SpecSource<'a> {
+    Version(&'a str),
+    Path(&'a Utf8Path),
+}
+
+impl SpecSource<'_> {
+    async fn load(
+        &self,
+        client: &mut SidekoClient,
+        name: Option<&String>,
+    ) -> CliResult<serde_json::Value> {
+        match self {
+            SpecSource::Version(version) => load_version(client, name, version).await,
+            SpecSource::Path(path) => {
+                let content = std::fs::read_to_string(path).map_err(|e| {
+                    CliError::io_custom(format!("failed reading openapi from path: {path}"), e)
+                })?;
+                parse_spec(&content, path.as_str())
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for SpecSource<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SpecSource::Version(version) => write!(f, "{version}"),
+            SpecSource::Path(path) => write!(f, "{path}"),
+        }
+    }
+}
+
+/// loads the openapi spec of a version of the api
+pub(super) async fn load_version(
+    client: &mut SidekoClient,
+    name: Option<&String>,
+    version: &str,
+) -> CliResult<serde_json::Value> {
+    let name = manifest::api_name(name)?;
+    let openapi_res = client
+        .api()
+        .spec()
+        .get_openapi(GetOpenapiRequest {
+            api_name: name.clone(),
+            api_version: ApiVersion::Str(version.to_string()),
+        })
+        .await?;
+
+    parse_spec(&openapi_res.openapi, &format!("{name}@{version}"))
+}
+
+/// parses a json or yaml openapi spec
+pub(super) fn parse_spec(content: &str, source: &str) -> CliResult<serde_json::Value> {
+    if let Ok(spec) = serde_json::from_str(content) {
+        return Ok(spec);
+    }
+
+    serde_norway::from_str(content).map_err(|e| {
+        CliError::general_debug(format!("failed parsing openapi spec: {source}"), e)
+            .with_code(ErrorCode::InvalidInput)
+    })
+}
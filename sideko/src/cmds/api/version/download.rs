@@ -7,13 +7,17 @@ use sideko_rest_api::{
     resources::api::spec::GetOpenapiRequest,
 };
 
-use crate::{result::CliResult, styles::fmt_green, utils::get_sideko_client};
+use crate::{
+    result::CliResult,
+    styles::fmt_green,
+    utils::{get_sideko_client, manifest},
+};
 
 #[derive(clap::Args)]
 pub struct ApiVersionDownloadCommand {
-    /// api name or id e.g. my-api
+    /// api name or id e.g. my-api [default: `api.name` of sideko.toml]
     #[arg(long)]
-    pub name: String,
+    pub name: Option<String>,
 
     /// version to update (e.g. `2.1.5` or `latest`)
     #[arg(long, default_value = "latest")]
@@ -30,17 +34,18 @@ pub struct ApiVersionDownloadCommand {
 }
 impl ApiVersionDownloadCommand {
     pub async fn handle(&self) -> CliResult<()> {
+        let name = manifest::api_name(self.name.as_ref())?;
         let mut client = get_sideko_client();
         let openapi_res = client
             .api()
             .spec()
             .get_openapi(GetOpenapiRequest {
-                api_name: self.name.clone(),
+                api_name: name.clone(),
                 api_version: ApiVersion::Str(self.version.clone()),
             })
             .await?;
 
-        let default_stem = format!("{}-{}", &self.name, &self.version);
+        let default_stem = format!("{}-{}", &name, &self.version);
 
         let dest = match &self.output {
             Some(o) => {
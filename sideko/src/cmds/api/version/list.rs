@@ -1,59 +1,79 @@
+use log::info;
 use sideko_rest_api::resources::api::spec::ListRequest;
-use tabled::settings::{object::Rows, Color};
 
 use crate::{
-    cmds::DisplayOutput,
+    cmds::{DisplayOutput, ListOptions},
     result::CliResult,
-    utils::{self, get_sideko_client},
+    utils::{self, archive::ArchivedVersions, get_sideko_client, manifest, template::Template},
 };
 
 use super::tabled::TabledApiSpec;
 
 #[derive(clap::Args)]
 pub struct ApiVersionListCommand {
-    /// api name or id e.g. my-api
+    /// api name or id e.g. my-api [default: `api.name` of sideko.toml]
     #[arg(long)]
-    pub name: String,
+    pub name: Option<String>,
 
     /// limit results to most recent N versions
     #[arg(long)]
     pub limit: Option<usize>,
 
-    /// display result as a raw json or prettified
+    /// include versions archived with `api version archive`
+    #[arg(long)]
+    pub archived: bool,
+
+    /// display result format
     #[arg(long, default_value = "pretty")]
     pub display: DisplayOutput,
+
+    /// render each result with a go template instead e.g. `{{.version}} {{.mock_server.url}}`
+    #[arg(long, value_parser = Template::parse, conflicts_with = "display")]
+    pub format: Option<Template>,
+
+    #[command(flatten)]
+    pub list: ListOptions,
 }
 impl ApiVersionListCommand {
     pub async fn handle(&self) -> CliResult<()> {
+        let name = manifest::api_name(self.name.as_ref())?;
         let mut client = get_sideko_client();
         let mut versions = client
             .api()
             .spec()
             .list(ListRequest {
-                api_name: self.name.clone(),
+                api_name: name.clone(),
             })
             .await?;
 
-        if let Some(limit) = self.limit {
-            versions = versions[0..versions.len().min(limit)].to_vec();
+        if !self.archived {
+            let archived = ArchivedVersions::load();
+            let before = versions.len();
+            versions.retain(|v| !archived.contains(v));
+            if versions.len() < before {
+                info!(
+                    "{} archived versions hidden, list them with --archived",
+                    before - versions.len()
+                );
+            }
         }
 
-        match &self.display {
-            DisplayOutput::Raw => utils::logging::log_json_raw(&versions),
-            DisplayOutput::Pretty => {
-                let org = client.org().get().await?;
-                let iter = versions.into_iter().map(|version| TabledApiSpec {
-                    version,
-                    org_subdomain: org.subdomain.clone(),
-                });
-                let mut table = tabled::Table::new(iter);
-                utils::tabled::header_panel(&mut table, "api versions");
-                table.modify(Rows::single(1), Color::BOLD);
-
-                utils::logging::log_table(table);
-            }
+        if let Some(limit) = self.limit {
+            versions = versions[0..versions.len().min(limit)].to_vec();
         }
 
-        Ok(())
+        let org = client.org().get().await?;
+        let rows = versions.into_iter().map(|version| TabledApiSpec {
+            version,
+            org_subdomain: org.subdomain.clone(),
+        });
+        utils::listing::print_list(
+            &self.display,
+            self.format.as_ref(),
+            "api versions",
+            &self.list,
+            rows,
+            |r| &r.version,
+        )
     }
 }
@@ -1,6 +1,9 @@
 use crate::result::CliResult;
 
+mod archive;
 mod create;
+mod delete;
+mod diff;
 mod download;
 mod list;
 pub(crate) mod tabled;
@@ -16,6 +19,14 @@ pub enum ApiVersionSubcommand {
     Update(update::ApiVersionUpdateCommand),
     /// downloads the openapi specification of an api version
     Download(download::ApiVersionDownloadCommand),
+    /// compares two api versions or local specs, classifying breaking changes
+    Diff(diff::ApiVersionDiffCommand),
+    /// permanently deletes an api version
+    Delete(delete::ApiVersionDeleteCommand),
+    /// hides an api version from `api version list` on this machine
+    Archive(archive::ApiVersionArchiveCommand),
+    /// shows an archived api version in `api version list` again
+    Unarchive(archive::ApiVersionArchiveCommand),
 }
 impl ApiVersionSubcommand {
     pub async fn handle(&self) -> CliResult<()> {
@@ -24,6 +35,10 @@ impl ApiVersionSubcommand {
             ApiVersionSubcommand::Create(cmd) => cmd.handle().await,
             ApiVersionSubcommand::Update(cmd) => cmd.handle().await,
             ApiVersionSubcommand::Download(cmd) => cmd.handle().await,
+            ApiVersionSubcommand::Diff(cmd) => cmd.handle().await,
+            ApiVersionSubcommand::Delete(cmd) => cmd.handle().await,
+            ApiVersionSubcommand::Archive(cmd) => cmd.handle(true).await,
+            ApiVersionSubcommand::Unarchive(cmd) => cmd.handle(false).await,
         }
     }
 }
@@ -1,6 +1,8 @@
 use sideko_rest_api::models::ApiSpec;
 
-use crate::utils::url_builder::ApiUrl;
+use crate::utils::{spec_diff::Change, url_builder::ApiUrl};
+
+use super::create::VersionProposal;
 
 pub struct TabledApiSpec {
     pub version: ApiSpec,
@@ -35,3 +37,72 @@ impl tabled::Tabled for TabledApiSpec {
         ]
     }
 }
+
+pub struct TabledSpecChange {
+    pub change: Change,
+}
+impl tabled::Tabled for TabledSpecChange {
+    const LENGTH: usize = 5;
+
+    fn fields(&self) -> Vec<std::borrow::Cow<'_, str>> {
+        let impact = if self.change.breaking {
+            "🔴 breaking"
+        } else {
+            "🟢 non-breaking"
+        };
+        vec![
+            impact.into(),
+            self.change.kind.as_str().into(),
+            self.change.element.as_str().into(),
+            self.change.location.as_str().into(),
+            self.change.detail.as_str().into(),
+        ]
+    }
+
+    fn headers() -> Vec<std::borrow::Cow<'static, str>> {
+        vec![
+            "impact".into(),
+            "change".into(),
+            "element".into(),
+            "location".into(),
+            "detail".into(),
+        ]
+    }
+}
+
+pub struct TabledVersionProposal {
+    pub proposal: VersionProposal,
+}
+impl tabled::Tabled for TabledVersionProposal {
+    const LENGTH: usize = 4;
+
+    fn fields(&self) -> Vec<std::borrow::Cow<'_, str>> {
+        vec![
+            self.proposal.api.as_str().into(),
+            self.proposal
+                .latest_version
+                .as_deref()
+                .unwrap_or_default()
+                .into(),
+            self.proposal
+                .proposed_version
+                .as_deref()
+                .unwrap_or("chosen by sideko")
+                .into(),
+            self.proposal
+                .bump
+                .map(|b| b.as_str())
+                .unwrap_or_default()
+                .into(),
+        ]
+    }
+
+    fn headers() -> Vec<std::borrow::Cow<'static, str>> {
+        vec![
+            "api".into(),
+            "latest version".into(),
+            "proposed version".into(),
+            "bump".into(),
+        ]
+    }
+}
@@ -1,20 +1,19 @@
 use camino::Utf8PathBuf;
 use sideko_rest_api::{models::ApiVersion, resources::api::spec::PatchRequest, UploadFile};
-use tabled::settings::{object::Rows, Color};
 
 use crate::{
     cmds::DisplayOutput,
     result::{CliError, CliResult},
-    utils::{self, get_sideko_client},
+    utils::{self, get_sideko_client, manifest, template::Template},
 };
 
 use super::tabled::TabledApiSpec;
 
 #[derive(clap::Args)]
 pub struct ApiVersionUpdateCommand {
-    /// api name or id e.g. my-api
+    /// api name or id e.g. my-api [default: `api.name` of sideko.toml]
     #[arg(long)]
-    pub name: String,
+    pub name: Option<String>,
 
     /// version to update (e.g. `2.1.5` or `latest`)
     #[arg(long, default_value = "latest")]
@@ -42,12 +41,17 @@ pub struct ApiVersionUpdateCommand {
     #[arg(long)]
     pub mock: Option<bool>,
 
-    /// display result as a raw json or prettified
+    /// display result format
     #[arg(long, default_value = "pretty")]
     pub display: DisplayOutput,
+
+    /// render the result with a go template instead e.g. `{{.version}}`
+    #[arg(long, value_parser = Template::parse, conflicts_with = "display")]
+    pub format: Option<Template>,
 }
 impl ApiVersionUpdateCommand {
     pub async fn handle(&self) -> CliResult<()> {
+        let name = manifest::api_name(self.name.as_ref())?;
         let mut client = get_sideko_client();
         let openapi = if let Some(path) = &self.spec {
             Some(UploadFile::from_path(path.as_str()).map_err(|e| {
@@ -61,7 +65,7 @@ impl ApiVersionUpdateCommand {
             .api()
             .spec()
             .patch(PatchRequest {
-                api_name: self.name.clone(),
+                api_name: name.clone(),
                 api_version: ApiVersion::Str(self.version.clone()),
                 version: self.new_version.clone(),
                 mock_server_enabled: self.mock,
@@ -71,21 +75,18 @@ impl ApiVersionUpdateCommand {
             })
             .await?;
 
-        match &self.display {
-            DisplayOutput::Raw => utils::logging::log_json_raw(&updated_version),
-            DisplayOutput::Pretty => {
-                let org = client.org().get().await?;
-                let mut table = tabled::Table::new([TabledApiSpec {
-                    version: updated_version,
-                    org_subdomain: org.subdomain.clone(),
-                }]);
-                utils::tabled::header_panel(&mut table, "updated api version");
-                table.modify(Rows::single(1), Color::BOLD);
-
-                utils::logging::log_table(table);
-            }
+        if let Some(template) = &self.format {
+            return template.print(&updated_version);
+        }
+        if self.display.is_structured() {
+            return utils::output::print_data(&self.display, &updated_version);
         }
 
-        Ok(())
+        let org = client.org().get().await?;
+        let row = TabledApiSpec {
+            version: updated_version,
+            org_subdomain: org.subdomain.clone(),
+        };
+        utils::output::print_rows(&self.display, "updated api version", [row])
     }
 }
@@ -1,12 +1,13 @@
 use crate::{
     cli::SidekoCli,
-    result::{CliError, CliResult},
+    result::{CliError, CliResult, ErrorCode},
     styles::fmt_green,
 };
 use clap::{Args, CommandFactory};
 use clap_complete::{generate, Shell};
 use dirs::home_dir;
 use inquire::Confirm;
+use log::info;
 use std::fs::{self, File};
 use std::io::Write;
 use std::path::{Path, PathBuf};
@@ -66,18 +67,18 @@ impl AutocompleteCommand {
         // Update shell RC file
         self.update_rc_file(&rc_path, &completion_path)?;
 
-        println!(
+        info!(
             "{} installed {} completions for {}",
             fmt_green("✓"),
             self.shell,
             name
         );
-        println!(
+        info!(
             "{} saved completion script: {}",
             fmt_green("✓"),
             completion_path.display()
         );
-        println!(
+        info!(
             "{} saved updated RC file: {}",
             fmt_green("✓"),
             rc_path.display()
@@ -87,10 +88,7 @@ impl AutocompleteCommand {
     }
 
     fn get_shell_paths(&self) -> CliResult<(PathBuf, PathBuf)> {
-        let home = home_dir().ok_or(CliError::General {
-            msg: "could not find home directory".to_string(),
-            debug: None,
-        })?;
+        let home = home_dir().ok_or(CliError::general("could not find home directory"))?;
 
         let (completion_path, rc_file) = match self.shell {
             Shell::Bash => (
@@ -109,10 +107,10 @@ impl AutocompleteCommand {
                 "config.fish",
             ),
             _ => {
-                return Err(CliError::General {
-                    msg: format!("Unsupported shell: {:?}", self.shell),
-                    debug: None,
-                })
+                return Err(
+                    CliError::general(format!("Unsupported shell: {:?}", self.shell))
+                        .with_code(ErrorCode::InvalidInput),
+                )
             }
         };
 
@@ -0,0 +1,33 @@
+use crate::{
+    result::{CliError, CliResult, ErrorCode},
+    utils::{
+        config::{mask_secret, ConfigKey},
+        output,
+    },
+};
+
+#[derive(clap::Args)]
+pub struct ConfigGetCommand {
+    /// config key to read
+    pub key: ConfigKey,
+
+    /// display secret values (e.g. the api key) without masking
+    #[arg(long)]
+    pub reveal: bool,
+}
+impl ConfigGetCommand {
+    pub async fn handle(&self) -> CliResult<()> {
+        let (val, _source) = self.key.resolve()?.ok_or_else(|| {
+            CliError::general(format!("`{}` is not set", self.key.name()))
+                .with_code(ErrorCode::Config)
+        })?;
+
+        if self.key.is_secret() && !self.reveal {
+            output::print_line(mask_secret(&val));
+        } else {
+            output::print_line(val);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,75 @@
+use crate::{
+    cmds::{DisplayOutput, ListOptions},
+    result::CliResult,
+    utils::{
+        self,
+        config::{mask_secret, ConfigKey},
+        template::Template,
+    },
+};
+
+#[derive(clap::Args)]
+pub struct ConfigListCommand {
+    /// display secret values (e.g. the api key) without masking
+    #[arg(long)]
+    pub reveal: bool,
+
+    /// display result format
+    #[arg(long, default_value = "pretty")]
+    pub display: DisplayOutput,
+
+    /// render each result with a go template instead e.g. `{{.key}}={{.value}}`
+    #[arg(long, value_parser = Template::parse, conflicts_with = "display")]
+    pub format: Option<Template>,
+
+    #[command(flatten)]
+    pub list: ListOptions,
+}
+impl ConfigListCommand {
+    pub async fn handle(&self) -> CliResult<()> {
+        let mut rows = vec![];
+        for key in ConfigKey::all() {
+            let (value, source) = match key.resolve()? {
+                Some((val, source)) => {
+                    let val = if key.is_secret() && !self.reveal {
+                        mask_secret(&val)
+                    } else {
+                        val
+                    };
+                    (Some(val), Some(source.to_string()))
+                }
+                None => (None, None),
+            };
+            rows.push(ConfigRow {
+                key: key.name().into(),
+                env_var: key.to_string(),
+                value,
+                source,
+            });
+        }
+
+        utils::listing::print_list(
+            &self.display,
+            self.format.as_ref(),
+            "config",
+            &self.list,
+            rows,
+            |r| r,
+        )
+    }
+}
+
+#[derive(tabled::Tabled, serde::Serialize)]
+struct ConfigRow {
+    key: String,
+    #[tabled(rename = "env var")]
+    env_var: String,
+    #[tabled(display = "display_option")]
+    value: Option<String>,
+    #[tabled(display = "display_option")]
+    source: Option<String>,
+}
+
+fn display_option(val: &Option<String>) -> String {
+    val.clone().unwrap_or_else(|| "-".into())
+}
@@ -1,10 +1,28 @@
 use crate::result::CliResult;
 
 mod autocomplete;
+mod get;
+mod list;
+mod profile;
+mod set;
+mod unset;
 
 #[derive(clap::Subcommand)]
 pub enum ConfigSubcommand {
+    // ------------ SUB-COMMANDS ------------
+    /// manage configuration profiles for multiple organizations and environments
+    #[command(subcommand)]
+    Profile(profile::ProfileSubcommand),
+
     // ------------ COMMANDS ------------
+    /// display the effective value of a config key
+    Get(get::ConfigGetCommand),
+    /// persist a config key in the config file (or credential store for secrets)
+    Set(set::ConfigSetCommand),
+    /// remove a persisted config key
+    Unset(unset::ConfigUnsetCommand),
+    /// list all config keys, their effective values, and where they were loaded from
+    List(list::ConfigListCommand),
     /// writes shell completion for the cli to stdout
     Autocomplete(autocomplete::AutocompleteCommand),
 }
@@ -12,7 +30,21 @@ pub enum ConfigSubcommand {
 impl ConfigSubcommand {
     pub async fn handle(&self) -> CliResult<()> {
         match self {
+            ConfigSubcommand::Profile(cmd) => cmd.handle().await,
+            ConfigSubcommand::Get(cmd) => cmd.handle().await,
+            ConfigSubcommand::Set(cmd) => cmd.handle().await,
+            ConfigSubcommand::Unset(cmd) => cmd.handle().await,
+            ConfigSubcommand::List(cmd) => cmd.handle().await,
             ConfigSubcommand::Autocomplete(cmd) => cmd.handle().await,
         }
     }
+
+    /// secret values passed as arguments
+    pub fn secrets(&self) -> Vec<String> {
+        match self {
+            ConfigSubcommand::Profile(cmd) => cmd.secrets(),
+            ConfigSubcommand::Set(cmd) if cmd.key.is_secret() => vec![cmd.value.clone()],
+            _ => vec![],
+        }
+    }
 }
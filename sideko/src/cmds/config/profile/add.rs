@@ -0,0 +1,57 @@
+use log::info;
+
+use crate::{
+    result::{CliError, CliResult, ErrorCode},
+    styles::fmt_green,
+    utils::config::{ConfigKey, Profile},
+};
+
+#[derive(clap::Args)]
+pub struct ProfileAddCommand {
+    /// name of the profile (only alphanumeric characters, dashes, and underscores, e.g. `staging`)
+    pub name: String,
+
+    /// sideko api base url to use with this profile (e.g. `https://api.sideko.dev/v1`)
+    #[arg(long)]
+    pub base_url: Option<String>,
+
+    /// api key to store in the os-native key service for this profile
+    #[arg(long)]
+    pub api_key: Option<String>,
+
+    /// make the new profile the active profile
+    #[arg(long = "use")]
+    pub use_profile: bool,
+}
+impl ProfileAddCommand {
+    pub async fn handle(&self) -> CliResult<()> {
+        let profile = Profile::new(&self.name)?;
+        if profile.exists()? {
+            return Err(
+                CliError::general(format!("profile `{profile}` already exists"))
+                    .with_code(ErrorCode::Config),
+            );
+        }
+
+        // the profile is considered created once its dotenv exists
+        if let Some(base_url) = &self.base_url {
+            ConfigKey::ApiBaseUrl.set_profile_env(&profile, base_url)?;
+        } else {
+            ConfigKey::ApiBaseUrl.unset_profile_env(&profile)?;
+        }
+
+        if let Some(key) = &self.api_key {
+            ConfigKey::ApiKey.set_profile_credential(&profile, key)?;
+        }
+        info!("{} profile `{profile}` created", fmt_green("✔"));
+
+        if self.use_profile {
+            ConfigKey::Profile.set_profile_env(&Profile::default_profile(), &profile)?;
+            info!("{} now using profile `{profile}`", fmt_green("✔"));
+        } else if self.api_key.is_none() {
+            info!("authenticate the profile with `sideko login --profile {profile}`");
+        }
+
+        Ok(())
+    }
+}
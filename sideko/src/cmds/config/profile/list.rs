@@ -0,0 +1,69 @@
+use crate::{
+    cmds::{DisplayOutput, ListOptions},
+    result::CliResult,
+    utils::{
+        self,
+        config::{ConfigKey, Profile},
+        template::Template,
+    },
+};
+
+#[derive(clap::Args)]
+pub struct ProfileListCommand {
+    /// display result format
+    #[arg(long, default_value = "pretty")]
+    pub display: DisplayOutput,
+
+    /// render each result with a go template instead e.g. `{{.name}}`
+    #[arg(long, value_parser = Template::parse, conflicts_with = "display")]
+    pub format: Option<Template>,
+
+    #[command(flatten)]
+    pub list: ListOptions,
+}
+impl ProfileListCommand {
+    pub async fn handle(&self) -> CliResult<()> {
+        let mut rows = vec![];
+        for profile in Profile::list()? {
+            rows.push(ProfileRow {
+                active: profile.is_active(),
+                config_path: profile.config_path()?.to_string(),
+                base_url: profile
+                    .read_env(&ConfigKey::ApiBaseUrl)?
+                    .unwrap_or_default(),
+                api_key_stored: ConfigKey::ApiKey.get_profile_credential(&profile).is_some(),
+                name: profile.name,
+            });
+        }
+
+        utils::listing::print_list(
+            &self.display,
+            self.format.as_ref(),
+            "profiles",
+            &self.list,
+            rows,
+            |r| r,
+        )
+    }
+}
+
+#[derive(tabled::Tabled, serde::Serialize)]
+struct ProfileRow {
+    name: String,
+    #[tabled(display = "display_active")]
+    active: bool,
+    #[tabled(rename = "base url")]
+    base_url: String,
+    #[tabled(rename = "api key stored")]
+    api_key_stored: bool,
+    #[tabled(rename = "config path")]
+    config_path: String,
+}
+
+fn display_active(active: &bool) -> String {
+    if *active {
+        "✔".into()
+    } else {
+        String::new()
+    }
+}
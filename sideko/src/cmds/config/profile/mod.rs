@@ -0,0 +1,38 @@
+use crate::result::CliResult;
+
+mod add;
+mod list;
+mod remove;
+mod switch;
+
+#[derive(clap::Subcommand)]
+pub enum ProfileSubcommand {
+    // ------------ COMMANDS ------------
+    /// list all configuration profiles
+    List(list::ProfileListCommand),
+    /// create a new configuration profile
+    Add(add::ProfileAddCommand),
+    /// remove a configuration profile and its stored credentials
+    Remove(remove::ProfileRemoveCommand),
+    /// set the profile used when `--profile` is not provided
+    Use(switch::ProfileUseCommand),
+}
+
+impl ProfileSubcommand {
+    pub async fn handle(&self) -> CliResult<()> {
+        match self {
+            ProfileSubcommand::List(cmd) => cmd.handle().await,
+            ProfileSubcommand::Add(cmd) => cmd.handle().await,
+            ProfileSubcommand::Remove(cmd) => cmd.handle().await,
+            ProfileSubcommand::Use(cmd) => cmd.handle().await,
+        }
+    }
+
+    /// secret values passed as arguments
+    pub fn secrets(&self) -> Vec<String> {
+        match self {
+            ProfileSubcommand::Add(cmd) => cmd.api_key.iter().cloned().collect(),
+            _ => vec![],
+        }
+    }
+}
@@ -0,0 +1,44 @@
+use log::info;
+
+use crate::{
+    result::{CliError, CliResult, ErrorCode},
+    styles::fmt_green,
+    utils::config::{ConfigKey, Profile},
+};
+
+#[derive(clap::Args)]
+pub struct ProfileRemoveCommand {
+    /// name of the profile to remove
+    pub name: String,
+}
+impl ProfileRemoveCommand {
+    pub async fn handle(&self) -> CliResult<()> {
+        let profile = Profile::new(&self.name)?;
+        if profile.is_default() {
+            return Err(CliError::general("the default profile cannot be removed")
+                .with_code(ErrorCode::Config));
+        }
+        if !profile.exists()? {
+            return Err(
+                CliError::general(format!("profile `{profile}` does not exist"))
+                    .with_code(ErrorCode::Config),
+            );
+        }
+
+        ConfigKey::ApiKey.unset_profile_credential(&profile)?;
+        let cfg_path = profile.config_path()?;
+        std::fs::remove_file(&cfg_path).map_err(|e| {
+            CliError::io_custom(format!("failed removing profile config: {cfg_path}"), e)
+        })?;
+
+        // fall back to the default profile if the removed profile was persisted as active
+        let default_profile = Profile::default_profile();
+        if default_profile.read_env(&ConfigKey::Profile)?.as_deref() == Some(&profile.name) {
+            ConfigKey::Profile.unset_profile_env(&default_profile)?;
+            info!("profile `{profile}` was active, now using the `{default_profile}` profile");
+        }
+
+        info!("{} profile `{profile}` removed", fmt_green("✔"));
+        Ok(())
+    }
+}
@@ -0,0 +1,35 @@
+use log::info;
+
+use crate::{
+    result::{CliError, CliResult, ErrorCode},
+    styles::fmt_green,
+    utils::config::{ConfigKey, Profile},
+};
+
+#[derive(clap::Args)]
+pub struct ProfileUseCommand {
+    /// name of the profile to activate
+    pub name: String,
+}
+impl ProfileUseCommand {
+    pub async fn handle(&self) -> CliResult<()> {
+        let profile = Profile::new(&self.name)?;
+        if !profile.exists()? {
+            return Err(CliError::general(format!(
+                "profile `{profile}` does not exist, create it with `sideko config profile add {profile}`"
+            ))
+            .with_code(ErrorCode::Config));
+        }
+
+        // the active profile is persisted in the default profile's config
+        let default_profile = Profile::default_profile();
+        if profile.is_default() {
+            ConfigKey::Profile.unset_profile_env(&default_profile)?;
+        } else {
+            ConfigKey::Profile.set_profile_env(&default_profile, &profile)?;
+        }
+
+        info!("{} now using profile `{profile}`", fmt_green("✔"));
+        Ok(())
+    }
+}
@@ -0,0 +1,32 @@
+use log::{info, warn};
+
+use crate::{
+    result::CliResult,
+    styles::fmt_green,
+    utils::config::{ConfigKey, ConfigSource},
+};
+
+#[derive(clap::Args)]
+pub struct ConfigSetCommand {
+    /// config key to set
+    pub key: ConfigKey,
+
+    /// new value of the key
+    pub value: String,
+}
+impl ConfigSetCommand {
+    pub async fn handle(&self) -> CliResult<()> {
+        let key = &self.key;
+        key.validate(&self.value)?;
+
+        let prev = key.resolve()?;
+        key.set(&self.value)?;
+        info!("{} {} set", fmt_green("✔"), key.name());
+
+        if let Some((_, ConfigSource::Env)) = prev {
+            warn!("${key} is set in the environment and takes priority over the stored value");
+        }
+
+        Ok(())
+    }
+}
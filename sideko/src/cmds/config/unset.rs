@@ -0,0 +1,27 @@
+use log::{info, warn};
+
+use crate::{
+    result::CliResult,
+    styles::fmt_green,
+    utils::config::{ConfigKey, ConfigSource},
+};
+
+#[derive(clap::Args)]
+pub struct ConfigUnsetCommand {
+    /// config key to remove
+    pub key: ConfigKey,
+}
+impl ConfigUnsetCommand {
+    pub async fn handle(&self) -> CliResult<()> {
+        let key = &self.key;
+        let prev = key.resolve()?;
+        key.unset()?;
+        info!("{} {} unset", fmt_green("✔"), key.name());
+
+        if let Some((_, ConfigSource::Env)) = prev {
+            warn!("${key} is still set in the environment");
+        }
+
+        Ok(())
+    }
+}
@@ -11,16 +11,16 @@ use sideko_rest_api::{
 };
 
 use crate::{
-    result::{CliError, CliResult},
+    result::{CliError, CliResult, ErrorCode},
     styles::fmt_yellow,
-    utils::{get_sideko_client, spinner::Spinner},
+    utils::{get_sideko_client, manifest, spinner::Spinner, template::Template},
 };
 
 #[derive(clap::Args)]
 pub struct DocDeployCommand {
-    /// doc project name or id e.g. my-docs
+    /// doc project name or id e.g. my-docs [default: `doc.name` of sideko.toml]
     #[arg(long)]
-    pub name: String,
+    pub name: Option<String>,
 
     /// deploy to production [default: preview]
     #[arg(long)]
@@ -29,6 +29,10 @@ pub struct DocDeployCommand {
     /// exit command after successful trigger [default: waits until deployment completes]
     #[arg(long)]
     pub no_wait: bool,
+
+    /// print the deployment rendered with a go template e.g. `{{.id}} {{.status}}`
+    #[arg(long, value_parser = Template::parse)]
+    pub format: Option<Template>,
 }
 impl DocDeployCommand {
     fn is_terminal_status(&self, status: &DeploymentStatusEnum) -> bool {
@@ -83,7 +87,8 @@ impl DocDeployCommand {
                         deployment.status
                     ),
                     format!("deployment: {deployment_details}"),
-                ));
+                )
+                .with_code(ErrorCode::Api));
             }
             DeploymentStatusEnum::Error => {
                 sp.stop_error("deployment failed");
@@ -93,7 +98,8 @@ impl DocDeployCommand {
                         deployment.status
                     ),
                     format!("deployment: {deployment_details}"),
-                ));
+                )
+                .with_code(ErrorCode::Api));
             }
             DeploymentStatusEnum::Created
             | DeploymentStatusEnum::Building
@@ -102,7 +108,8 @@ impl DocDeployCommand {
                 return Err(CliError::general_debug(
                     format!("deployment polling terminated in `{}` status. polling should continue until terminal status", deployment.status),
                     format!("deployment: {deployment_details}"),
-                ));
+                )
+                .with_code(ErrorCode::Api));
             }
         }
 
@@ -110,6 +117,7 @@ impl DocDeployCommand {
     }
 
     pub async fn handle(&self) -> CliResult<()> {
+        let name = manifest::doc_name(self.name.as_ref())?;
         let mut client = get_sideko_client();
 
         let target = if self.prod {
@@ -121,14 +129,14 @@ impl DocDeployCommand {
         let doc_project = client
             .doc()
             .get(doc::GetRequest {
-                doc_name: self.name.clone(),
+                doc_name: name.clone(),
             })
             .await?;
         let deployment = client
             .doc()
             .deployment()
             .trigger(TriggerRequest {
-                doc_name: self.name.clone(),
+                doc_name: name.clone(),
                 target: target.clone(),
                 doc_version_id: None,
             })
@@ -140,11 +148,14 @@ impl DocDeployCommand {
             "deployment (id={}) metadata: {}",
             &deployment.id,
             serde_json::to_string_pretty(&deployment.metadata)
-                .unwrap_or_else(|_| deployment.metadata.to_string())
+                .unwrap_or_else(|_| format!("{:?}", deployment.metadata))
         );
 
         if self.no_wait {
             info!("--no-wait specified, not polling until completion");
+            if let Some(template) = &self.format {
+                template.print(&deployment)?;
+            }
             return Ok(());
         }
 
@@ -174,6 +185,9 @@ impl DocDeployCommand {
                 };
 
                 info!("site available at: {url}");
+                if let Some(template) = &self.format {
+                    template.print(&deployment)?;
+                }
                 Ok(())
             }
             Err(_) => Err(CliError::general(
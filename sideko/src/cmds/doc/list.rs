@@ -1,9 +1,7 @@
-use tabled::settings::{object::Rows, Color};
-
 use crate::{
-    cmds::DisplayOutput,
+    cmds::{DisplayOutput, ListOptions},
     result::CliResult,
-    utils::{self, get_sideko_client},
+    utils::{self, get_sideko_client, template::Template},
 };
 
 use super::tabled::TabledDocProject;
@@ -13,27 +11,31 @@ pub struct DocListCommand {
     /// display result format
     #[arg(long, default_value = "pretty")]
     pub display: DisplayOutput,
+
+    /// render each result with a go template instead e.g. `{{.domains.preview}}`
+    #[arg(long, value_parser = Template::parse, conflicts_with = "display")]
+    pub format: Option<Template>,
+
+    #[command(flatten)]
+    pub list: ListOptions,
 }
 impl DocListCommand {
     pub async fn handle(&self) -> CliResult<()> {
         let mut client = get_sideko_client();
         let doc_projects = client.doc().list().await?;
 
-        match &self.display {
-            DisplayOutput::Raw => utils::logging::log_json_raw(&doc_projects),
-            DisplayOutput::Pretty => {
-                let org = client.org().get().await?;
-                let mut table =
-                    tabled::Table::new(doc_projects.into_iter().map(|doc| TabledDocProject {
-                        doc,
-                        org_subdomain: org.subdomain.clone(),
-                    }));
-                utils::tabled::header_panel(&mut table, "documentation projects");
-                table.modify(Rows::single(1), Color::BOLD);
-                utils::logging::log_table(table);
-            }
-        }
-
-        Ok(())
+        let org = client.org().get().await?;
+        let rows = doc_projects.into_iter().map(|doc| TabledDocProject {
+            doc,
+            org_subdomain: org.subdomain.clone(),
+        });
+        utils::listing::print_list(
+            &self.display,
+            self.format.as_ref(),
+            "documentation projects",
+            &self.list,
+            rows,
+            |r| &r.doc,
+        )
     }
 }
@@ -1,11 +1,14 @@
+use std::sync::{Arc, Mutex};
+
 use camino::Utf8PathBuf;
-use log::{debug, info};
-use rocket::error;
+use log::{debug, info, warn};
+use ring::rand::{SecureRandom, SystemRandom};
+use rocket::http::Status;
 use sideko_rest_api::resources::auth::ExchangeCodeRequest;
 use tokio::time;
 
 use crate::{
-    result::{CliError, CliResult},
+    result::{CliError, CliResult, ErrorCode},
     styles::{fmt_green, fmt_red},
     utils,
 };
@@ -16,120 +19,296 @@ pub(crate) struct LoginCommand {
     #[arg(long)]
     pub key: Option<String>,
 
-    /// path to file to store api key, default: $HOME/.sideko
+    /// path to file to store api key, default: config of the active profile ($HOME/.sideko)
     #[arg(long)]
     pub output: Option<Utf8PathBuf>,
+
+    /// do not open a browser or start a local callback server, instead print the login url
+    /// and paste the auth code displayed after logging in (e.g. over ssh or in dev containers)
+    #[arg(long)]
+    pub no_browser: bool,
+
+    /// local port for the login callback server, falls back to a free port if it is taken
+    #[arg(long, default_value = "65530")]
+    pub port: u16,
 }
 
 impl LoginCommand {
     pub async fn handle(&self) -> CliResult<()> {
-        info!("🔑🔑🔑 this CLI uses your OS keychain to securely store your Sideko API key...");
+        info!("🔑🔑🔑 this CLI uses your OS keychain (or an encrypted file if unavailable) to securely store your Sideko API key...");
         if let Some(key) = &self.key {
-            utils::config::ConfigKey::ApiKey.set_keyring(key)?;
+            utils::config::ConfigKey::ApiKey.set_credential(key)?;
             info!("{} CLI authenticated", fmt_green("✔"));
             return Ok(());
         }
 
-        let port = 65530;
-        let wait_secs = 5 * 60; // 5 min default
         let output = if let Some(o) = &self.output {
             o.clone()
         } else {
-            utils::config::get_default_config_path()?
+            utils::config::get_config_path()?
         };
 
-        // open browser for login
-        let login_url = url::Url::parse_with_params(
-            &format!("{}/auth/login_url", utils::config::get_base_url()),
-            &[
-                ("cli_output", output.to_string()),
-                ("cli_port", port.to_string()),
-            ],
-        )
-        .map_err(|e| CliError::general_debug("Failed building login URL", format!("{e:?}")))?;
+        if self.no_browser {
+            self.headless_login(&output).await
+        } else {
+            self.browser_login(&output).await
+        }
+    }
 
-        info!("continue by logging in with the browser popup...");
+    /// prints the login url and exchanges the auth code pasted by the user
+    async fn headless_login(&self, output: &Utf8PathBuf) -> CliResult<()> {
+        let login_url = build_login_url(&[
+            ("cli_output", output.to_string()),
+            ("cli_mode", "code".into()),
+        ])?;
 
-        if let Err(e) = open::that(login_url.as_str()) {
-            log::warn!(
-                "failed opening browser for login, please navigate to `{login_url}` to complete login"
-            );
-            log::debug!("{:?}", e);
+        info!("open the following link in any browser and log in:\n\n    {login_url}\n");
+        let code = inquire::Password::new("auth code:")
+            .with_display_mode(inquire::PasswordDisplayMode::Masked)
+            .without_confirmation()
+            .with_help_message("paste the auth code displayed after logging in")
+            .prompt()?;
+        let code = code.trim();
+        if code.is_empty() {
+            return Err(CliError::general("no auth code provided").with_code(ErrorCode::Auth));
         }
 
-        debug!("if the browser does not open, you can log in via this link: {login_url}");
+        exchange_code(code.into()).await?;
+        info!("{} CLI authenticated", fmt_green("✔"));
+
+        Ok(())
+    }
+
+    /// opens the login url in the browser and waits for the auth callback on a local server
+    async fn browser_login(&self, output: &Utf8PathBuf) -> CliResult<()> {
+        let wait_secs = 5 * 60; // 5 min default
+        let login_state = Arc::new(LoginState::new()?);
+
+        info!("continue by logging in with the browser popup...");
+        info!("no browser available? re-run with `sideko login --no-browser`");
         time::sleep(time::Duration::from_secs(1)).await; // allow user to read info log
 
-        // launch callback server & wait for callback
-        debug!("starting callback server on port {port}... will wait {wait_secs} seconds for auth callback");
-        let server_config = rocket::Config {
-            port,
-            log_level: rocket::config::LogLevel::Off,
-            ..Default::default()
+        // launch callback server & wait for callback, the browser is opened once the
+        // server is bound so the login url always points at the bound port
+        debug!("starting callback server... will wait {wait_secs} seconds for auth callback");
+        let server_future = async {
+            match serve_callback(self.port, output, &login_state).await {
+                Err(e) if matches!(e.kind(), rocket::error::ErrorKind::Bind(_)) => {
+                    warn!(
+                        "port {} is not available, using a free port for the login callback",
+                        self.port
+                    );
+                    serve_callback(0, output, &login_state).await
+                }
+                res => res,
+            }
         };
-        let server_future = rocket::build()
-            .mount(
-                "/",
-                rocket::routes![login_callback, login_success, login_failure],
-            )
-            .configure(server_config)
-            .launch();
         let timeout = time::timeout(time::Duration::from_secs(wait_secs), server_future).await;
 
-        if timeout.is_err() {
-            Err(CliError::general(format!(
+        match timeout {
+            Err(_) => Err(CliError::general(format!(
                 "authentication was not completed within {wait_secs} seconds"
-            )))
-        } else {
-            Ok(())
+            ))
+            .with_code(ErrorCode::Auth)),
+            Ok(Err(e)) => Err(CliError::general_debug(
+                "login callback server failed",
+                format!("{e:?}"),
+            )),
+            Ok(Ok(_)) => match login_state.take_result() {
+                Some(Ok(())) => {
+                    info!("{} CLI authenticated", fmt_green("✔"));
+                    Ok(())
+                }
+                Some(Err(e)) => Err(e),
+                None => Err(CliError::general("authentication was not completed")
+                    .with_code(ErrorCode::Auth)),
+            },
         }
     }
 }
 
+/// runs the callback server on the port (0 for a free port) until the login finished,
+/// the login url is opened once the server is bound
+async fn serve_callback(
+    port: u16,
+    output: &Utf8PathBuf,
+    login_state: &Arc<LoginState>,
+) -> Result<rocket::Rocket<rocket::Ignite>, rocket::Error> {
+    let server_config = rocket::Config {
+        port,
+        log_level: rocket::config::LogLevel::Off,
+        ..Default::default()
+    };
+    let output = output.to_string();
+    let nonce = login_state.nonce.clone();
+    rocket::build()
+        .mount(
+            "/",
+            rocket::routes![login_callback, login_success, login_failure],
+        )
+        .manage(login_state.clone())
+        .attach(rocket::fairing::AdHoc::on_liftoff(
+            "open login url",
+            |rocket| {
+                Box::pin(async move {
+                    let port = rocket.config().port;
+                    debug!("callback server listening on port {port}");
+                    open_login_url(&[
+                        ("cli_output", output),
+                        ("cli_port", port.to_string()),
+                        ("state", nonce),
+                    ]);
+                })
+            },
+        ))
+        .configure(server_config)
+        .launch()
+        .await
+}
+
+/// opens the login url in the browser, logging it if that fails
+fn open_login_url(params: &[(&str, String)]) {
+    let login_url = match build_login_url(params) {
+        Ok(url) => url,
+        Err(e) => {
+            e.log();
+            return;
+        }
+    };
+
+    if let Err(e) = open::that(login_url.as_str()) {
+        log::warn!(
+            "failed opening browser for login, please navigate to `{login_url}` to complete login"
+        );
+        log::debug!("{:?}", e);
+    }
+    debug!("if the browser does not open, you can log in via this link: {login_url}");
+}
+
+/// state shared with the callback server for a single browser login attempt
+struct LoginState {
+    /// random value passed through the login url, callbacks must echo it back
+    nonce: String,
+    result: Mutex<Option<CliResult<()>>>,
+}
+
+impl LoginState {
+    fn new() -> CliResult<Self> {
+        let mut bytes = [0u8; 32];
+        SystemRandom::new().fill(&mut bytes).map_err(|e| {
+            CliError::general_debug("failed generating login state", format!("{e:?}"))
+        })?;
+        let nonce = bytes.iter().map(|b| format!("{b:02x}")).collect();
+
+        Ok(Self {
+            nonce,
+            result: Mutex::new(None),
+        })
+    }
+
+    /// rejects requests that were not initiated by this login attempt, the server keeps
+    /// waiting for the real one
+    fn check(&self, state: Option<&str>) -> Result<(), Rejection> {
+        if state == Some(self.nonce.as_str()) {
+            return Ok(());
+        }
+
+        warn!("ignoring login callback with missing or mismatched state");
+        Err((
+            Status::Forbidden,
+            rocket::response::content::RawHtml(FAILURE_HTML),
+        ))
+    }
+
+    /// records the outcome of the login, the first outcome wins
+    fn set_result(&self, res: CliResult<()>) {
+        let mut result = self.result.lock().unwrap_or_else(|e| e.into_inner());
+        if result.is_none() {
+            *result = Some(res);
+        }
+    }
+
+    fn take_result(&self) -> Option<CliResult<()>> {
+        self.result.lock().unwrap_or_else(|e| e.into_inner()).take()
+    }
+}
+
+fn build_login_url(params: &[(&str, String)]) -> CliResult<url::Url> {
+    url::Url::parse_with_params(
+        &format!("{}/auth/login_url", utils::config::get_base_url()),
+        params,
+    )
+    .map_err(|e| CliError::general_debug("Failed building login URL", format!("{e:?}")))
+}
+
+/// exchanges the auth code for an api key and stores it in the credential store
+async fn exchange_code(code: String) -> CliResult<()> {
+    let mut client = utils::get_sideko_client();
+    let exchanged = client
+        .auth()
+        .exchange_code(ExchangeCodeRequest { code })
+        .await
+        .map_err(|e| CliError::api_custom("failed exchanging auth code for api key", e))?;
+
+    utils::config::ConfigKey::ApiKey.set_credential(exchanged.api_key)
+}
+
 // ------------ ROUTES ------------
 
+/// response to requests that were not initiated by this login attempt
+type Rejection = (Status, rocket::response::content::RawHtml<&'static str>);
+
 static SUCCESS_HTML: &str = include_str!("../html/success.html");
 static FAILURE_HTML: &str = include_str!("../html/failure.html");
 
-#[rocket::get("/success")]
+#[rocket::get("/success?<state>")]
 async fn login_success(
+    state: Option<String>,
     shutdown: rocket::Shutdown,
-) -> rocket::response::content::RawHtml<&'static str> {
-    info!("{} CLI authenticated", fmt_green("✔"));
+    login_state: &rocket::State<Arc<LoginState>>,
+) -> Result<rocket::response::content::RawHtml<&'static str>, Rejection> {
+    login_state.check(state.as_deref())?;
     shutdown.notify();
-    rocket::response::content::RawHtml(SUCCESS_HTML)
+    Ok(rocket::response::content::RawHtml(SUCCESS_HTML))
 }
 
-#[rocket::get("/failure")]
+#[rocket::get("/failure?<state>")]
 async fn login_failure(
+    state: Option<String>,
     shutdown: rocket::Shutdown,
-) -> rocket::response::content::RawHtml<&'static str> {
+    login_state: &rocket::State<Arc<LoginState>>,
+) -> Result<rocket::response::content::RawHtml<&'static str>, Rejection> {
+    login_state.check(state.as_deref())?;
+    login_state.set_result(Err(CliError::general(format!(
+        "{} CLI authentication failed",
+        fmt_red("x")
+    ))
+    .with_code(ErrorCode::Auth)));
     shutdown.notify();
-    error!("{} CLI authentication failed", fmt_red("x"));
-    rocket::response::content::RawHtml(FAILURE_HTML)
+    Ok(rocket::response::content::RawHtml(FAILURE_HTML))
 }
 
-#[rocket::get("/login?<code>&<output>")]
-async fn login_callback(code: String, output: String) -> rocket::response::Redirect {
-    // exchange code for api key
-    let mut client = utils::get_sideko_client();
-    match client
-        .auth()
-        .exchange_code(ExchangeCodeRequest { code })
-        .await
-    {
-        Ok(exchanged) => {
-            std::env::set_var(utils::config::ConfigKey::ConfigPath.to_string(), &output);
-            if let Err(e) = utils::config::ConfigKey::ApiKey.set_keyring(exchanged.api_key) {
-                e.log();
-                return rocket::response::Redirect::to(rocket::uri!(login_failure));
-            }
+#[rocket::get("/login?<code>&<state>")]
+async fn login_callback(
+    code: String,
+    state: Option<String>,
+    login_state: &rocket::State<Arc<LoginState>>,
+) -> Result<rocket::response::Redirect, Rejection> {
+    login_state.check(state.as_deref())?;
 
-            rocket::response::Redirect::to(rocket::uri!(login_success))
+    let state = Some(login_state.nonce.clone());
+    match exchange_code(code).await {
+        Ok(_) => {
+            login_state.set_result(Ok(()));
+            Ok(rocket::response::Redirect::to(rocket::uri!(login_success(
+                state
+            ))))
         }
         Err(e) => {
-            CliError::api_custom("failed exchanging auth code for api key", e).log();
-            rocket::response::Redirect::to(rocket::uri!(login_success))
+            login_state.set_result(Err(e));
+            Ok(rocket::response::Redirect::to(rocket::uri!(login_failure(
+                state
+            ))))
         }
     }
 }
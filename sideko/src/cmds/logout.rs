@@ -8,7 +8,7 @@ impl LogoutCommand {
     pub async fn handle(&self) -> CliResult<()> {
         std::env::remove_var(ConfigKey::ApiKey.to_string());
         ConfigKey::ApiKey.unset_env()?;
-        ConfigKey::ApiKey.unset_keyring()?;
+        ConfigKey::ApiKey.unset_credential()?;
 
         info!("{} logout successful", fmt_green("✔"));
         Ok(())
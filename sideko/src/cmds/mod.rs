@@ -8,10 +8,10 @@ mod api;
 pub(crate) use api::ApiSubcommand;
 
 mod account;
-pub(crate) use account::AccountSubcommand;
+pub(crate) use account::{AccountStatusCommand, AccountSubcommand};
 
 mod sdk;
-pub(crate) use sdk::SdkSubcommand;
+pub(crate) use sdk::{SdkLang, SdkSubcommand};
 
 mod doc;
 pub(crate) use doc::DocSubcommand;
@@ -21,7 +21,41 @@ pub(crate) use config::ConfigSubcommand;
 
 #[derive(clap::ValueEnum, serde::Serialize, Default, Debug, Clone)]
 pub enum DisplayOutput {
+    /// pretty printed json
     #[default]
     Raw,
+    /// tables for the terminal
     Pretty,
+    Yaml,
+    /// one json object per line
+    Jsonl,
+    Csv,
+    /// markdown tables, e.g. for pull request descriptions
+    Markdown,
+}
+
+impl DisplayOutput {
+    /// whether the result is serialized as is rather than rendered as table rows
+    pub fn is_structured(&self) -> bool {
+        matches!(self, Self::Raw | Self::Yaml | Self::Jsonl)
+    }
+}
+
+/// options shared by commands listing resources
+///
+/// fields are either table columns (e.g. `name`, `link`) or fields of the raw model,
+/// nested fields separated with dots (e.g. `mock_server.enabled`)
+#[derive(clap::Args, Debug, Clone, Default)]
+pub struct ListOptions {
+    /// comma separated fields to display e.g. `name,versions`
+    #[arg(long, value_delimiter = ',')]
+    pub columns: Vec<String>,
+
+    /// sort by a field, ascending unless suffixed with `:desc` e.g. `version:desc` (repeat to break ties)
+    #[arg(long)]
+    pub sort: Vec<String>,
+
+    /// only show rows matching `<field><op><value>` with the ops `=`, `!=`, `~` (contains), `!~`, `>`, `>=`, `<`, `<=` e.g. `name~pet` (repeat to combine)
+    #[arg(long)]
+    pub filter: Vec<String>,
 }
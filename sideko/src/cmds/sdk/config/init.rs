@@ -6,19 +6,19 @@ use sideko_rest_api::{models::ApiVersion, resources::sdk::config::InitRequest};
 
 use crate::{
     cmds::sdk::{LlmCodingAssistantItem, SdkModuleStructure},
-    result::{CliError, CliResult},
-    utils::{self, get_sideko_client},
+    result::{CliError, CliResult, ErrorCode},
+    utils::{self, get_sideko_client, manifest},
 };
 
 #[derive(clap::Args)]
 pub struct SdkConfigInitCommand {
-    /// api name or id e.g. my-api
+    /// api name or id e.g. my-api [default: `api.name` of sideko.toml]
     #[arg(long)]
-    pub api_name: String,
+    pub api_name: Option<String>,
 
-    /// generate config for specific version (e.g. `2.1.5`)
-    #[arg(long, default_value = "latest")]
-    pub api_version: String,
+    /// generate config for specific version (e.g. `2.1.5`) [default: `api.version` of sideko.toml or `latest`]
+    #[arg(long)]
+    pub api_version: Option<String>,
 
     /// default module structure that should be generated
     /// for the SDK config.
@@ -41,14 +41,17 @@ pub struct SdkConfigInitCommand {
 
 impl SdkConfigInitCommand {
     pub async fn handle(&self) -> CliResult<()> {
+        let api_name = manifest::api_name(self.api_name.as_ref())?;
         let mut client = get_sideko_client();
 
         let config_res = client
             .sdk()
             .config()
             .init(InitRequest {
-                api_name: self.api_name.clone(),
-                api_version: Some(ApiVersion::Str(self.api_version.clone())),
+                api_name,
+                api_version: Some(ApiVersion::Str(manifest::api_version(
+                    self.api_version.as_ref(),
+                ))),
                 default_module_structure: self.module_structure.clone().map(|m| m.0),
                 llm_coding_assistant: self
                     .llm_coding_assistants
@@ -63,6 +66,7 @@ impl SdkConfigInitCommand {
                 "failed to parse config yaml as UTF-8 string",
                 format!("{e:?}"),
             )
+            .with_code(ErrorCode::Api)
         })?;
         fs::write(&self.output, &config).map_err(|e| {
             CliError::io_custom(format!("failed writing config to {}", &self.output), e)
@@ -5,23 +5,28 @@ use log::info;
 use sideko_rest_api::{models::ApiVersion, resources::sdk::config::SyncRequest, UploadFile};
 
 use crate::{
-    result::{CliError, CliResult},
-    utils::{self, get_sideko_client},
+    cmds::sdk::SdkLang,
+    result::{CliError, CliResult, ErrorCode},
+    utils::{self, get_sideko_client, manifest},
 };
 
 #[derive(clap::Args)]
 pub struct SdkConfigSyncCommand {
-    /// sync config with specific api version (e.g. `2.1.5`)
-    #[arg(long, default_value = "latest")]
-    pub api_version: String,
+    /// sync config with specific api version (e.g. `2.1.5`) [default: `api.version` of sideko.toml or `latest`]
+    #[arg(long)]
+    pub api_version: Option<String>,
 
     /// sync config with local openapi specification
     #[arg(long, value_parser = crate::utils::validators::validate_file_json_yaml)]
     pub spec: Option<Utf8PathBuf>,
 
-    /// config to sync
+    /// config to sync [default: `config` of the matching `[[sdk]]` in sideko.toml]
     #[arg(long, value_parser = crate::utils::validators::validate_file_yaml)]
-    pub config: Utf8PathBuf,
+    pub config: Option<Utf8PathBuf>,
+
+    /// select the `[[sdk]]` of sideko.toml whose config should be synced
+    #[arg(long)]
+    pub lang: Option<SdkLang>,
 
     /// custom output path of sdk config (must be .yaml or .yml) [defaults to same path as --config]
     #[arg(
@@ -33,6 +38,8 @@ pub struct SdkConfigSyncCommand {
 
 impl SdkConfigSyncCommand {
     pub async fn handle(&self) -> CliResult<()> {
+        let sdk = manifest::sdk(self.lang.as_ref(), self.config.as_ref(), None)?;
+        let config = manifest::sdk_config(self.config.as_ref(), sdk)?;
         let mut client = get_sideko_client();
 
         let (api_version, openapi) = if let Some(spec) = &self.spec {
@@ -43,7 +50,12 @@ impl SdkConfigSyncCommand {
                 })?),
             )
         } else {
-            (Some(ApiVersion::Str(self.api_version.clone())), None)
+            (
+                Some(ApiVersion::Str(manifest::api_version(
+                    self.api_version.as_ref(),
+                ))),
+                None,
+            )
         };
 
         let synced_res = client
@@ -51,23 +63,21 @@ impl SdkConfigSyncCommand {
             .config()
             .sync(SyncRequest {
                 api_version,
-                config: UploadFile::from_path(self.config.as_str()).map_err(|e| {
-                    CliError::io_custom(
-                        format!("failed reading config from path: {}", &self.config),
-                        e,
-                    )
+                config: UploadFile::from_path(config.as_str()).map_err(|e| {
+                    CliError::io_custom(format!("failed reading config from path: {config}"), e)
                 })?,
                 openapi,
             })
             .await?;
 
         // load yml as string and save to output
-        let output = self.output.as_ref().unwrap_or(&self.config);
+        let output = self.output.as_ref().unwrap_or(&config);
         let config = String::from_utf8(synced_res.content.to_vec()).map_err(|e| {
             CliError::general_debug(
                 "failed to parse synced config yaml as UTF-8 string",
                 format!("{e:?}"),
             )
+            .with_code(ErrorCode::Api)
         })?;
         fs::write(output, &config).map_err(|e| {
             CliError::io_custom(format!("failed writing synced config to {output}"), e)
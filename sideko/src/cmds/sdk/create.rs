@@ -10,8 +10,8 @@ use tar::Archive;
 use crate::{
     result::{CliError, CliResult},
     utils::{
-        self,
-        {get_sideko_client, spinner::Spinner},
+        self, get_sideko_client, manifest,
+        progress::{Phase, Progress},
     },
 };
 
@@ -19,21 +19,21 @@ use super::SdkLang;
 
 #[derive(clap::Args)]
 pub struct SdkCreateCommand {
-    /// path to sdk config
+    /// path to sdk config [default: `config` of the matching `[[sdk]]` in sideko.toml]
     #[arg(long, value_parser = crate::utils::validators::validate_file_yaml)]
-    pub config: Utf8PathBuf,
+    pub config: Option<Utf8PathBuf>,
 
-    /// programming language to generate
+    /// programming language to generate [default: `lang` of the matching `[[sdk]]` in sideko.toml]
     #[arg(long)]
-    pub lang: SdkLang,
+    pub lang: Option<SdkLang>,
 
     /// semantic version of generated sdk
     #[arg(long, default_value = "0.1.0")]
     pub version: semver::Version,
 
-    /// generate sdk for a specific version of the api (e.g. `2.1.5`)
-    #[arg(long, default_value = "latest")]
-    pub api_version: String,
+    /// generate sdk for a specific version of the api (e.g. `2.1.5`) [default: `api.version` of sideko.toml or `latest`]
+    #[arg(long)]
+    pub api_version: Option<String>,
 
     /// include github actions for testing and publishing the sdk in the generation
     #[arg(long)]
@@ -54,23 +54,26 @@ pub struct SdkCreateCommand {
 
 impl SdkCreateCommand {
     pub async fn handle(&self) -> CliResult<()> {
+        let sdk = manifest::sdk(self.lang.as_ref(), self.config.as_ref(), None)?;
+        let lang = SdkLang::resolve(self.lang.as_ref(), sdk)?;
+        let config = manifest::sdk_config(self.config.as_ref(), sdk)?;
+
         let mut client = get_sideko_client();
 
         let start = chrono::Utc::now();
 
-        let mut sp = Spinner::new(format!("🪄  generating {} sdk", self.lang.0));
+        let mut sp = Progress::new(format!("🪄  generating {} sdk", lang.0), Phase::Processing);
         let sdk_res = match client
             .sdk()
             .generate(GenerateRequest {
-                api_version: Some(ApiVersion::Str(self.api_version.clone())),
-                config: UploadFile::from_path(self.config.as_str()).map_err(|e| {
-                    CliError::io_custom(
-                        format!("Failed reading config from path: {}", &self.config),
-                        e,
-                    )
+                api_version: Some(ApiVersion::Str(manifest::api_version(
+                    self.api_version.as_ref(),
+                ))),
+                config: UploadFile::from_path(config.as_str()).map_err(|e| {
+                    CliError::io_custom(format!("Failed reading config from path: {config}"), e)
                 })?,
                 github_actions: Some(self.gh_actions),
-                language: self.lang.0.clone(),
+                language: lang.0.clone(),
                 sdk_version: Some(self.version.to_string()),
                 allow_lint_errors: Some(self.allow_lint_errors),
             })
@@ -79,8 +82,8 @@ impl SdkCreateCommand {
             Ok(r) => {
                 sp.stop_success(format!(
                     "{} {} sdk generated.",
-                    self.lang.emoji(),
-                    &self.lang.0.to_string()
+                    lang.emoji(),
+                    &lang.0.to_string()
                 ));
                 r
             }
@@ -103,16 +103,16 @@ impl SdkInitCommand {
 
         utils::logging::log_table(report_table);
 
-        println!(
+        info!(
             "\n{}",
             fmt_grey("To debug these errors further, run the following command in a separate terminal session:")
         );
-        println!(
+        info!(
             "{}",
             fmt_green(&format!("sideko api lint --spec {oas_path}"))
         );
-        println!("\n{}", fmt_grey("💡 Tip: Try copying the error table above and giving it to your coding agent to help resolve the issues."));
-        println!();
+        info!("\n{}", fmt_grey("💡 Tip: Try copying the error table above and giving it to your coding agent to help resolve the issues."));
+        info!("");
 
         Ok(false) // Has errors, cannot proceed
     }
@@ -284,8 +284,8 @@ impl SdkInitCommand {
         let llm_assistants = self.select_llm_coding_assistants()?;
 
         let init_cmd = SdkConfigInitCommand {
-            api_name: api.name.clone(),
-            api_version: version.version.clone(),
+            api_name: Some(api.name.clone()),
+            api_version: Some(version.version.clone()),
             module_structure: Some(SdkModuleStructure(mod_struct)),
             output: output.clone(),
             llm_coding_assistants: Some(llm_assistants),
@@ -397,10 +397,10 @@ impl SdkInitCommand {
                 json!(&lang)
             );
             let create_sdk_cmd = SdkCreateCommand {
-                config: config.clone(),
-                lang: SdkLang(lang),
+                config: Some(config.clone()),
+                lang: Some(SdkLang(lang)),
                 version: Version::new(0, 1, 0),
-                api_version: api_version.version.clone(),
+                api_version: Some(api_version.version.clone()),
                 gh_actions: true,
                 output: Utf8PathBuf::new().join("."),
                 allow_lint_errors: false,
@@ -655,7 +655,7 @@ impl FilePathCompleter {
             })
             .collect();
 
-        matches.sort_by(|a, b| b.1.cmp(&a.1));
+        matches.sort_by_key(|m| std::cmp::Reverse(m.1));
         matches
     }
 }
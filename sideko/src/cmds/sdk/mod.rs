@@ -5,7 +5,10 @@ use clap::{builder::PossibleValue, ValueEnum};
 use log::debug;
 use sideko_rest_api::models::{InitSdkConfigLlmCodingAssistantItemEnum, SdkLanguageEnum, SdkModuleStructureEnum};
 
-use crate::result::{CliError, CliResult};
+use crate::{
+    result::{CliError, CliResult, ErrorCode},
+    utils::manifest::ManifestSdk,
+};
 
 mod config;
 mod create;
@@ -50,6 +53,20 @@ impl SdkSubcommand {
 #[derive(Debug, Clone)]
 pub struct SdkLang(SdkLanguageEnum);
 impl SdkLang {
+    /// resolves the language from the flag or the selected sdk of the manifest
+    pub fn resolve(flag: Option<&SdkLang>, sdk: Option<&ManifestSdk>) -> CliResult<SdkLang> {
+        if let Some(lang) = flag {
+            return Ok(lang.clone());
+        }
+        match sdk {
+            Some(sdk) => Ok(sdk.lang.clone()),
+            None => Err(CliError::general(
+                "--lang is required (or add an `[[sdk]]` entry to a sideko.toml in this or a parent directory)",
+            )
+            .with_code(ErrorCode::InvalidInput)),
+        }
+    }
+
     pub fn emoji(&self) -> String {
         match &self.0 {
             SdkLanguageEnum::Go => "🐹".into(),
@@ -62,6 +79,34 @@ impl SdkLang {
     }
 }
 
+impl PartialEq for SdkLang {
+    fn eq(&self, other: &Self) -> bool {
+        std::mem::discriminant(&self.0) == std::mem::discriminant(&other.0)
+    }
+}
+
+impl std::fmt::Display for SdkLang {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for SdkLang {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let val = String::deserialize(deserializer)?;
+        SdkLang::from_str(&val, true).map_err(|_| {
+            let expected = SdkLang::value_variants()
+                .iter()
+                .map(|l| l.to_string())
+                .collect::<Vec<_>>()
+                .join(", ");
+            serde::de::Error::custom(format!(
+                "unknown language `{val}`, expected one of: {expected}"
+            ))
+        })
+    }
+}
+
 impl ValueEnum for SdkLang {
     fn value_variants<'a>() -> &'a [Self] {
         &[
@@ -148,7 +193,8 @@ impl SdkMetadata {
             return Err(CliError::general_debug(
                 format!("could not determine sdk id of this repository. are you sure {repo} is the root of a sideko sdk?"),
                 format!("sdk metadata path does not exist in repo: {md_path}"),
-            ));
+            )
+            .with_code(ErrorCode::InvalidInput));
         }
 
         let md_str = fs::read_to_string(&md_path).map_err(|e| {
@@ -156,6 +202,7 @@ impl SdkMetadata {
                 format!("could not determine sdk id of this repository. are you sure {repo} is the root of a sideko sdk?"),
                 format!("unable to read sdk metadata path to string {md_path}: {e:?}"),
             )
+            .with_code(ErrorCode::InvalidInput)
         })?;
         debug!("Found sdk metadata: {md_str}");
 
@@ -164,6 +211,7 @@ impl SdkMetadata {
                 "could not determine sdk id of this repository. are you sure this a sideko sdk?",
                 format!("unable to deserialize sdk metadata path to string {md_path}: {e:?}"),
             )
+            .with_code(ErrorCode::InvalidInput)
         })?;
         Ok(md)
     }
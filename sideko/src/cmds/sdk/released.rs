@@ -3,14 +3,21 @@ use log::info;
 use sideko_rest_api::resources::sdk::metadata::UpdateRequest;
 
 use crate::{
-    cmds::sdk::SdkMetadata, result::CliResult, styles::fmt_green, utils::get_sideko_client,
+    cmds::sdk::{SdkLang, SdkMetadata},
+    result::CliResult,
+    styles::fmt_green,
+    utils::{get_sideko_client, manifest},
 };
 
 #[derive(clap::Args)]
 pub struct SdkReleasedCommand {
-    /// path to root of sdk repo
-    #[arg(long, default_value = "./", value_parser = crate::utils::validators::validate_dir)]
-    pub repo: Utf8PathBuf,
+    /// path to root of sdk repo [default: `./`, or the `repo` of the `[[sdk]]` in sideko.toml selected with --lang]
+    #[arg(long, value_parser = crate::utils::validators::validate_dir)]
+    pub repo: Option<Utf8PathBuf>,
+
+    /// select the `[[sdk]]` of sideko.toml to mark as released
+    #[arg(long)]
+    pub lang: Option<SdkLang>,
 
     /// sdk id to be marked as released, if this is provided the command will not look for the sdk id via the --repo
     #[arg(long)]
@@ -22,7 +29,13 @@ impl SdkReleasedCommand {
         let sdk_id = if let Some(i) = &self.id {
             i.clone()
         } else {
-            SdkMetadata::load_from_repo(&self.repo)?.id
+            let sdk = match &self.lang {
+                Some(lang) => manifest::sdk(Some(lang), None, None)?,
+                None => None,
+            };
+            let repo = manifest::sdk_repo(self.repo.as_ref(), sdk)?
+                .unwrap_or_else(|| Utf8PathBuf::from("./"));
+            SdkMetadata::load_from_repo(&repo)?.id
         };
 
         let mut client = get_sideko_client();
@@ -1,6 +1,6 @@
-use std::{fs, io::Write, process, str};
+use std::{fs, io::Write, path::Path, process, str};
 
-use camino::Utf8PathBuf;
+use camino::{Utf8Path, Utf8PathBuf};
 use flate2::{write::GzEncoder, Compression};
 
 use log::debug;
@@ -12,29 +12,36 @@ use sideko_rest_api::{
 use tempfile::TempDir;
 
 use crate::{
-    cmds::sdk::SdkMetadata,
-    result::{CliError, CliResult},
-    utils::{get_sideko_client, spinner::Spinner},
+    cmds::sdk::{SdkLang, SdkMetadata},
+    result::{CliError, CliResult, ErrorCode},
+    utils::{
+        get_sideko_client, manifest,
+        progress::{Phase, Progress},
+    },
 };
 
 #[derive(clap::Args)]
 pub struct SdkUpdateCommand {
-    /// path to sdk config
+    /// path to sdk config [default: `config` of the matching `[[sdk]]` in sideko.toml]
     #[arg(long, value_parser = crate::utils::validators::validate_file_yaml)]
-    pub config: Utf8PathBuf,
+    pub config: Option<Utf8PathBuf>,
 
-    /// path to root of sdk repo
+    /// path to root of sdk repo [default: `repo` of the matching `[[sdk]]` in sideko.toml]
     #[arg(long, value_parser = crate::utils::validators::validate_dir)]
-    pub repo: Utf8PathBuf,
+    pub repo: Option<Utf8PathBuf>,
+
+    /// select the `[[sdk]]` of sideko.toml to update
+    #[arg(long)]
+    pub lang: Option<SdkLang>,
 
     /// semantic version (e.g. `2.1.5`) or version bump
     /// (`auto`, `patch`, `minor`, `major`, `rc-patch`, `rc-minor`, `rc-major`, `release`)
     #[arg(long, default_value = "auto")]
     pub version: String,
 
-    /// api version to update sdk with (e.g. `2.1.5`)
-    #[arg(long, default_value = "latest")]
-    pub api_version: String,
+    /// api version to update sdk with (e.g. `2.1.5`) [default: `api.version` of sideko.toml or `latest`]
+    #[arg(long)]
+    pub api_version: Option<String>,
 
     /// update the SDK even thought OpenAPI linting errors were caught in this API version
     #[arg(long)]
@@ -48,18 +55,19 @@ impl SdkUpdateCommand {
     ///     - the git repo is clean (no un-committed files)
     ///
     /// returns: the `.git` directory path within given path
-    fn validate_git_root(&self) -> CliResult<Utf8PathBuf> {
+    fn validate_git_root(&self, repo: &Utf8PathBuf) -> CliResult<Utf8PathBuf> {
         // validate .git is present
-        let git_dir = self.repo.join(".git");
+        let git_dir = repo.join(".git");
         if !(git_dir.is_dir() && git_dir.exists()) {
             return Err(CliError::general(format!(
                 "Path is not the root of a git repository, {git_dir} not present"
-            )));
+            ))
+            .with_code(ErrorCode::InvalidInput));
         }
 
         // validate clean repo
         let status_output = process::Command::new("git")
-            .current_dir(&self.repo)
+            .current_dir(repo)
             .args(["status", "--porcelain"])
             .output()
             .map_err(|e| {
@@ -78,7 +86,8 @@ impl SdkUpdateCommand {
                     stdout = str::from_utf8(&status_output.stdout).unwrap_or_default(),
                     stderr = str::from_utf8(&status_output.stderr).unwrap_or_default(),
                 )
-            ));
+            )
+            .with_code(ErrorCode::InvalidInput));
         }
 
         Ok(git_dir)
@@ -86,13 +95,18 @@ impl SdkUpdateCommand {
 
     pub async fn handle(&self) -> CliResult<()> {
         // validate and prep args
-        let git_root = self.validate_git_root()?;
-        let prev_sdk_id = SdkMetadata::load_from_repo(&self.repo)?.id;
-        let config = UploadFile::from_path(self.config.as_str()).map_err(|e| {
-            CliError::io_custom(
-                format!("failed reading config from path: {}", &self.config),
-                e,
+        let sdk = manifest::sdk(self.lang.as_ref(), self.config.as_ref(), self.repo.as_ref())?;
+        let repo = manifest::sdk_repo(self.repo.as_ref(), sdk)?.ok_or_else(|| {
+            CliError::general(
+                "--repo is required (or set `repo` of an `[[sdk]]` in a sideko.toml in this or a parent directory)",
             )
+            .with_code(ErrorCode::InvalidInput)
+        })?;
+        let config_path = manifest::sdk_config(self.config.as_ref(), sdk)?;
+        let git_root = self.validate_git_root(&repo)?;
+        let prev_sdk_id = SdkMetadata::load_from_repo(&repo)?.id;
+        let config = UploadFile::from_path(config_path.as_str()).map_err(|e| {
+            CliError::io_custom(format!("failed reading config from path: {config_path}"), e)
         })?;
 
         // Create a temporary directory for the tarred .git contents
@@ -100,26 +114,26 @@ impl SdkUpdateCommand {
             .map_err(|e| CliError::io_custom("failed creating temporary directory", e))?;
         debug!("created temp directory {:?}", temp_dir.path());
 
+        let start = chrono::Utc::now();
+        let mut sp = Progress::new("🪄  updating sdk", Phase::Tar);
+
         // tar .git in prep for update request
-        let archive_into = temp_dir.path().join("git.tar.gz");
-        debug!("tarring .git into {archive_into:?}...");
-        let mut tar_gz = fs::File::create(&archive_into)?;
-        let encoder = GzEncoder::new(&tar_gz, Compression::default());
-        let mut tar = tar::Builder::new(encoder);
-        tar.append_dir_all(".", git_root)?;
-        tar.into_inner()?.finish()?;
-        tar_gz.flush()?;
-        let prev_sdk_git = UploadFile::from_path(&archive_into.to_string_lossy())?;
-        debug!("tar complete: {} bytes", prev_sdk_git.content.len());
+        let prev_sdk_git = match tar_git_dir(&git_root, temp_dir.path(), &sp) {
+            Ok(f) => f,
+            Err(e) => {
+                sp.stop_error("failed tarring .git");
+                return Err(e);
+            }
+        };
 
+        sp.phase(Phase::Processing);
         let mut client = get_sideko_client();
-
-        let start = chrono::Utc::now();
-        let mut sp = Spinner::new("🪄  updating sdk");
         let patch_content = match client
             .sdk()
             .update(UpdateRequest {
-                api_version: Some(ApiVersion::Str(self.api_version.clone())),
+                api_version: Some(ApiVersion::Str(manifest::api_version(
+                    self.api_version.as_ref(),
+                ))),
                 config,
                 prev_sdk_git,
                 prev_sdk_id,
@@ -147,12 +161,12 @@ impl SdkUpdateCommand {
 
         // write and apply git patch
         let patch_filename = "sdk_update.patch";
-        let patch_path = self.repo.join(patch_filename);
+        let patch_path = repo.join(patch_filename);
         fs::write(&patch_path, &patch_content)
             .map_err(|e| CliError::io_custom("Failed writing sdk git patch file", e))?;
 
         let patch_output = process::Command::new("git")
-            .current_dir(&self.repo)
+            .current_dir(&repo)
             .arg("apply")
             .arg(patch_filename)
             .output()
@@ -181,3 +195,38 @@ impl SdkUpdateCommand {
         }
     }
 }
+
+/// gzipped tar of the `.git` directory, the tarred bytes are reported as progress
+fn tar_git_dir(git_dir: &Utf8Path, temp_dir: &Path, sp: &Progress) -> CliResult<UploadFile> {
+    let archive_into = temp_dir.join("git.tar.gz");
+    debug!("tarring .git into {archive_into:?}...");
+    let mut tar_gz = fs::File::create(&archive_into)?;
+    let encoder = GzEncoder::new(&tar_gz, Compression::default());
+    let mut tar =
+        tar::Builder::new(sp.writer(Phase::Tar, Some(tar_size(git_dir.as_std_path())), encoder));
+    tar.append_dir_all(".", git_dir)?;
+    tar.into_inner()?.into_inner().finish()?;
+    tar_gz.flush()?;
+    let prev_sdk_git = UploadFile::from_path(&archive_into.to_string_lossy())?;
+    debug!("tar complete: {} bytes", prev_sdk_git.content.len());
+
+    Ok(prev_sdk_git)
+}
+
+/// estimated size of the uncompressed tar of a directory, each entry has a 512 byte
+/// header and its content is padded to 512 byte blocks
+fn tar_size(dir: &Path) -> u64 {
+    fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| match entry.file_type() {
+            Ok(t) if t.is_dir() => 512 + tar_size(&entry.path()),
+            Ok(_) => {
+                let len = entry.metadata().map(|m| m.len()).unwrap_or_default();
+                512 + len.next_multiple_of(512)
+            }
+            Err(_) => 0,
+        })
+        .sum()
+}
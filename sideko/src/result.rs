@@ -1,19 +1,89 @@
-use std::io;
+use std::io::{self, Write};
 
 use log::{debug, error};
 
+/// stable error codes, each with a distinct process exit code, so scripts can tell
+/// failures apart. codes and exit codes must never be reused for a different failure
+#[derive(clap::ValueEnum, serde::Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCode {
+    /// unclassified failure
+    General,
+    /// invalid arguments or missing input (also used by usage errors)
+    InvalidInput,
+    /// invalid config, profile or sideko.toml
+    Config,
+    /// failed reading or writing local files
+    Io,
+    /// not logged in or the api key was rejected
+    Auth,
+    /// the requested resource does not exist
+    NotFound,
+    /// the sideko api returned an error or an unexpected response
+    Api,
+    /// too many requests, try again later
+    RateLimited,
+    /// the sideko api could not be reached (e.g. dns, proxy, tls or timeouts)
+    Network,
+    /// linting found errors in the openapi spec
+    LintErrors,
+    /// the cli must be updated to continue
+    UpdateRequired,
+    /// an interactive prompt was cancelled
+    Cancelled,
+    /// failed accessing the credential store
+    Credentials,
+    /// failed accessing the clipboard
+    Clipboard,
+    /// comparing api versions found breaking changes
+    BreakingChanges,
+}
+
+impl ErrorCode {
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            ErrorCode::General => 1,
+            ErrorCode::InvalidInput => 2,
+            ErrorCode::Config => 3,
+            ErrorCode::Io => 4,
+            ErrorCode::Auth => 5,
+            ErrorCode::NotFound => 6,
+            ErrorCode::Api => 7,
+            ErrorCode::RateLimited => 8,
+            ErrorCode::Network => 9,
+            ErrorCode::LintErrors => 10,
+            ErrorCode::UpdateRequired => 11,
+            ErrorCode::Cancelled => 12,
+            ErrorCode::Credentials => 13,
+            ErrorCode::Clipboard => 14,
+            ErrorCode::BreakingChanges => 15,
+        }
+    }
+}
+
+/// how a failed command reports its error on stderr
+#[derive(clap::ValueEnum, Debug, Clone, Default)]
+pub enum ErrorFormat {
+    /// human readable log lines
+    #[default]
+    Text,
+    /// a single json object with the error code, message, debug details and http status
+    Json,
+}
+
 #[derive(Debug)]
 pub enum CliError {
     General {
         msg: String,
         debug: Option<String>,
+        code: ErrorCode,
     },
     Io {
         err: io::Error,
         override_msg: Option<String>,
     },
     Api {
-        err: sideko_rest_api::Error,
+        err: Box<sideko_rest_api::Error>,
         override_msg: Option<String>,
     },
     Inquire {
@@ -35,13 +105,24 @@ impl CliError {
         CliError::General {
             msg: msg.to_string(),
             debug: None,
+            code: ErrorCode::General,
         }
     }
     pub fn general_debug<S: ToString, D: ToString>(msg: S, debug: D) -> Self {
         CliError::General {
             msg: msg.to_string(),
             debug: Some(debug.to_string()),
+            code: ErrorCode::General,
+        }
+    }
+
+    /// sets the code of a general error, other variants derive their code from the
+    /// underlying error
+    pub fn with_code(mut self, error_code: ErrorCode) -> Self {
+        if let CliError::General { code, .. } = &mut self {
+            *code = error_code;
         }
+        self
     }
 
     pub fn io_custom<S: ToString>(msg: S, err: io::Error) -> Self {
@@ -53,7 +134,7 @@ impl CliError {
     pub fn api_custom<S: ToString>(msg: S, err: sideko_rest_api::Error) -> Self {
         CliError::Api {
             override_msg: Some(msg.to_string()),
-            err,
+            err: Box::new(err),
         }
     }
     pub fn inquire_custom<S: ToString>(msg: S, err: inquire::InquireError) -> Self {
@@ -75,77 +156,197 @@ impl CliError {
         }
     }
 
-    pub fn log(&self) {
-        let err_log = match self {
-            CliError::General { msg, debug } => {
-                if let Some(d) = debug {
-                    debug!("{d}")
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            CliError::General { code, .. } => *code,
+            CliError::Io { .. } => ErrorCode::Io,
+            CliError::Keyring { .. } => ErrorCode::Credentials,
+            CliError::Arboard { .. } => ErrorCode::Clipboard,
+            CliError::Inquire { err, .. } => match err {
+                inquire::InquireError::OperationCanceled
+                | inquire::InquireError::OperationInterrupted => ErrorCode::Cancelled,
+                inquire::InquireError::NotTTY => ErrorCode::InvalidInput,
+                inquire::InquireError::IO(_) => ErrorCode::Io,
+                _ => ErrorCode::General,
+            },
+            CliError::Api { err, .. } => match err.as_ref() {
+                sideko_rest_api::Error::Io(_) => ErrorCode::Io,
+                sideko_rest_api::Error::Request(_) => ErrorCode::Network,
+                sideko_rest_api::Error::Api(e) => match e.status.as_u16() {
+                    401 | 403 => ErrorCode::Auth,
+                    404 => ErrorCode::NotFound,
+                    429 => ErrorCode::RateLimited,
+                    _ => ErrorCode::Api,
+                },
+                sideko_rest_api::Error::DeserializeJson(..)
+                | sideko_rest_api::Error::ContentType(_) => ErrorCode::Api,
+                sideko_rest_api::Error::Custom(_) => ErrorCode::General,
+            },
+        }
+    }
+
+    pub fn exit_code(&self) -> i32 {
+        self.code().exit_code()
+    }
+
+    /// status of the sideko api response that caused the error
+    pub fn http_status(&self) -> Option<u16> {
+        match self {
+            CliError::Api { err, .. } => match err.as_ref() {
+                sideko_rest_api::Error::Api(e) | sideko_rest_api::Error::ContentType(e) => {
+                    Some(e.status.as_u16())
                 }
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    pub fn log(&self) {
+        let details = self.details();
+        for d in &details.debug {
+            debug!("{d}");
+        }
+        if let Some(description) = &details.api_description {
+            error!("{description}");
+        }
+        error!("{}", details.msg);
+    }
+
+    /// writes the error as a single json object to stderr, debug details are always
+    /// included
+    pub fn log_json(&self) {
+        let details = self.details();
+        let mut debug = details.debug;
+        // the api description is the most specific message, keep the generic one as a detail
+        let message = match details.api_description {
+            Some(description) => {
+                debug.insert(0, details.msg);
+                description
+            }
+            None => details.msg,
+        };
+
+        let envelope = ErrorEnvelope {
+            code: self.code(),
+            exit_code: self.exit_code(),
+            message,
+            debug: (!debug.is_empty()).then(|| debug.join("\n")),
+            http_status: self.http_status(),
+        };
+        let _ = writeln!(
+            io::stderr().lock(),
+            "{}",
+            serde_json::to_string(&envelope).unwrap_or_default()
+        );
+    }
+
+    fn details(&self) -> ErrorDetails {
+        let mut debug = vec![];
+        let mut api_description = None;
+        let msg = match self {
+            CliError::General { msg, debug: d, .. } => {
+                debug.extend(d.clone());
                 msg.clone()
             }
             CliError::Io { override_msg, err } => {
-                debug!("{err:?}");
+                debug.push(format!("{err:?}"));
                 override_msg.clone().unwrap_or_else(|| err.to_string())
             }
             CliError::Keyring { override_msg, err } => {
-                debug!("{err:?}");
+                debug.push(format!("{err:?}"));
                 override_msg.clone().unwrap_or_else(|| err.to_string())
             }
             CliError::Arboard { override_msg, err } => {
-                debug!("{err:?}");
+                debug.push(format!("{err:?}"));
                 override_msg.clone().unwrap_or_else(|| err.to_string())
             }
             CliError::Inquire { err, override_msg } => {
-                debug!("{err:?}");
+                debug.push(format!("{err:?}"));
                 override_msg.clone().unwrap_or_else(|| err.to_string())
             }
             CliError::Api { override_msg, err } => {
-                match err {
-                    sideko_rest_api::Error::Io(e) => debug!("SDK IO Error: {e:?}"),
-                    sideko_rest_api::Error::Request(e) => debug!("SDK Request Error: {e:?}"),
+                match err.as_ref() {
+                    sideko_rest_api::Error::Io(e) => debug.push(format!("SDK IO Error: {e:?}")),
+                    sideko_rest_api::Error::Request(e) => {
+                        debug.push(format!("SDK Request Error: {e:?}"));
+                        // the request error alone rarely names the cause (e.g. an invalid certificate)
+                        api_description = Some(format!(
+                            "failed reaching the sideko api: {}",
+                            crate::utils::http::error_chain(e)
+                        ));
+                    }
                     sideko_rest_api::Error::DeserializeJson(e, json_str) => {
                         let res_json = serde_json::to_string_pretty(
                             &serde_json::from_str::<serde_json::Value>(json_str)
                                 .unwrap_or_default(),
                         )
                         .unwrap_or_else(|_| json_str.to_string());
-                        debug!("Deserializer Error: {e:?}");
-                        debug!("Raw JSON: {res_json}");
+                        debug.push(format!("Deserializer Error: {e:?}"));
+                        debug.push(format!("Raw JSON: {res_json}"));
                     }
                     sideko_rest_api::Error::Api(e) | sideko_rest_api::Error::ContentType(e) => {
-                        debug!("Response headers: {:#?}", &e.headers);
+                        debug.push(format!("Response headers: {:#?}", &e.headers));
                         if let Ok(val) = e.json::<serde_json::Value>() {
-                            log::debug!(
+                            debug.push(format!(
                                 "Body: {}",
                                 serde_json::to_string_pretty(&val)
                                     .unwrap_or_else(|_| val.to_string())
-                            );
+                            ));
                             if let Some(serde_json::Value::String(description)) =
                                 val.get("description")
                             {
-                                error!("{description}");
+                                api_description = Some(description.clone());
                             }
                         } else if let Ok(text) = std::str::from_utf8(&e.content) {
-                            log::debug!("Body: {text}",);
+                            debug.push(format!("Body: {text}"));
                         } else {
-                            log::debug!("Unable to display body ({} bytes)", e.content.len())
+                            debug.push(format!(
+                                "Unable to display body ({} bytes)",
+                                e.content.len()
+                            ))
                         }
                     }
-                    sideko_rest_api::Error::Custom(msg) => debug!("{msg}"),
+                    sideko_rest_api::Error::Custom(msg) => debug.push(msg.clone()),
                 }
 
                 override_msg.clone().unwrap_or_else(|| err.to_string())
             }
         };
 
-        error!("{err_log}");
+        ErrorDetails {
+            msg,
+            api_description,
+            debug,
+        }
     }
 }
 
+/// human readable parts of an error
+struct ErrorDetails {
+    msg: String,
+    /// error description returned by the sideko api
+    api_description: Option<String>,
+    /// only shown in verbose mode
+    debug: Vec<String>,
+}
+
+/// machine readable error printed with `--error-format json`
+#[derive(serde::Serialize)]
+struct ErrorEnvelope {
+    code: ErrorCode,
+    exit_code: i32,
+    message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    debug: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    http_status: Option<u16>,
+}
+
 impl From<sideko_rest_api::Error> for CliError {
     fn from(err: sideko_rest_api::Error) -> Self {
         Self::Api {
-            err,
+            err: Box::new(err),
             override_msg: None,
         }
     }
@@ -1,5 +1,69 @@
+use std::{io::IsTerminal, sync::OnceLock};
+
 use anstyle::{AnsiColor, Color, Style};
 use clap::builder::Styles;
+use regex::Regex;
+
+/// when to use colors (and spinners) in the terminal
+#[derive(clap::ValueEnum, Debug, Clone, Default)]
+pub enum ColorChoice {
+    /// only when writing to a terminal and `NO_COLOR` is not set
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+/// whether colors are enabled for stdout (results) and stderr (logs, spinners, prompts)
+struct ColorSettings {
+    stdout: bool,
+    stderr: bool,
+}
+
+static COLOR_SETTINGS: OnceLock<ColorSettings> = OnceLock::new();
+
+impl ColorSettings {
+    fn new(choice: &ColorChoice) -> Self {
+        let enabled = |is_terminal: bool| match choice {
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+            ColorChoice::Auto => {
+                is_terminal && std::env::var_os("NO_COLOR").is_none_or(|val| val.is_empty())
+            }
+        };
+
+        Self {
+            stdout: enabled(std::io::stdout().is_terminal()),
+            stderr: enabled(std::io::stderr().is_terminal()),
+        }
+    }
+
+    fn get() -> &'static Self {
+        COLOR_SETTINGS.get_or_init(|| Self::new(&ColorChoice::Auto))
+    }
+}
+
+/// resolves the color choice, must be called before anything is styled
+pub fn init_color(choice: &ColorChoice) {
+    let _ = COLOR_SETTINGS.set(ColorSettings::new(choice));
+    if !stderr_color() {
+        inquire::set_global_render_config(inquire::ui::RenderConfig::empty());
+    }
+}
+
+pub fn stdout_color() -> bool {
+    ColorSettings::get().stdout
+}
+
+pub fn stderr_color() -> bool {
+    ColorSettings::get().stderr
+}
+
+/// removes ansi escape sequences, e.g. from tables styled with colors
+pub fn strip_ansi(text: &str) -> String {
+    let pattern = Regex::new(r"\x1b\[[0-9;]*m").expect("invalid ansi escape regex pattern");
+    pattern.replace_all(text, "").into_owned()
+}
 
 pub fn get_styles() -> Styles {
     Styles::styled()
@@ -35,7 +99,11 @@ pub fn get_styles() -> Styles {
         .placeholder(Style::new().fg_color(Some(Color::Ansi(AnsiColor::White))))
 }
 
+/// styled text is only used for diagnostics on stderr
 fn fmt_style(msg: &str, style: &Style) -> String {
+    if !stderr_color() {
+        return msg.to_string();
+    }
     format!("{style}{msg}{style:#}")
 }
 
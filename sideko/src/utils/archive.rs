@@ -0,0 +1,80 @@
+use std::collections::BTreeMap;
+
+use log::debug;
+use sideko_rest_api::models::ApiSpec;
+
+use crate::result::{CliError, CliResult};
+
+use super::config;
+
+/// api versions hidden from `api version list`, archiving is local to this machine
+/// and does not change the version in sideko
+#[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
+pub(crate) struct ArchivedVersions {
+    /// archived versions by api version id
+    versions: BTreeMap<String, ArchivedVersion>,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub(crate) struct ArchivedVersion {
+    pub api: String,
+    pub version: String,
+    pub archived_at: String,
+}
+
+impl ArchivedVersions {
+    /// loads the archived versions, a missing or invalid file archives nothing
+    pub(crate) fn load() -> Self {
+        let Ok(path) = config::get_archive_path() else {
+            return Self::default();
+        };
+        let Ok(content) = std::fs::read_to_string(&path) else {
+            return Self::default();
+        };
+
+        serde_json::from_str(&content).unwrap_or_else(|e| {
+            debug!("ignoring invalid archived versions file {path}: {e}");
+            Self::default()
+        })
+    }
+
+    pub(crate) fn save(&self) -> CliResult<()> {
+        let path = config::get_archive_path()?;
+        let content = serde_json::to_string_pretty(self)
+            .map_err(|e| CliError::general_debug("failed serializing archived versions", e))?;
+        std::fs::write(&path, content)
+            .map_err(|e| CliError::io_custom(format!("failed writing {path}"), e))
+    }
+
+    pub(crate) fn contains(&self, version: &ApiSpec) -> bool {
+        self.versions.contains_key(&version.id)
+    }
+
+    /// returns false if the version was already archived
+    pub(crate) fn archive(&mut self, version: &ApiSpec) -> bool {
+        if self.contains(version) {
+            return false;
+        }
+        self.versions.insert(
+            version.id.clone(),
+            ArchivedVersion {
+                api: version.api.name.clone(),
+                version: version.version.clone(),
+                archived_at: chrono::Utc::now().to_rfc3339(),
+            },
+        );
+        true
+    }
+
+    /// returns false if the version was not archived
+    pub(crate) fn unarchive(&mut self, version: &ApiSpec) -> bool {
+        self.versions.remove(&version.id).is_some()
+    }
+
+    /// forgets all archived versions of an api, e.g. once it is deleted
+    pub(crate) fn remove_api(&mut self, api: &str) -> bool {
+        let before = self.versions.len();
+        self.versions.retain(|_, archived| archived.api != api);
+        before != self.versions.len()
+    }
+}
@@ -2,21 +2,224 @@ use std::{env, fmt::Display, str::FromStr};
 
 use camino::Utf8PathBuf;
 use log::{debug, warn};
+use regex::Regex;
 
-use crate::result::{CliError, CliResult};
+use crate::result::{CliError, CliResult, ErrorCode};
 
+use super::{
+    credentials::{credentials_file, CredentialBackend, CredentialStore},
+    http,
+};
+
+/// name of the profile used when neither `--profile` nor `SIDEKO_PROFILE` is set
+pub(crate) const DEFAULT_PROFILE: &str = "default";
+
+/// seconds an update check result is cached for when `SIDEKO_UPDATE_CHECK_TTL` is not set
+pub(crate) const DEFAULT_UPDATE_CHECK_TTL: u64 = 60 * 60 * 24;
+
+#[derive(Debug, Clone, PartialEq)]
 pub enum ConfigKey {
     ConfigPath,
     ApiKey,
     ApiBaseUrl,
+    Profile,
+    CredentialStore,
+    NoUpdateCheck,
+    UpdateCheckTtl,
+    Proxy,
+    NoProxy,
+    CaCerts,
+    Timeout,
+    LogFile,
 }
 impl ConfigKey {
-    /// reads the content of the configured dotenv file
+    /// all configurable keys in the order they are displayed
+    pub fn all() -> &'static [ConfigKey] {
+        <Self as clap::ValueEnum>::value_variants()
+    }
+
+    /// environment variable holding the key
+    pub fn env_var(&self) -> &'static str {
+        match self {
+            ConfigKey::ApiKey => "SIDEKO_API_KEY",
+            ConfigKey::ApiBaseUrl => "SIDEKO_BASE_URL",
+            ConfigKey::ConfigPath => "SIDEKO_CONFIG_PATH",
+            ConfigKey::Profile => "SIDEKO_PROFILE",
+            ConfigKey::CredentialStore => "SIDEKO_CREDENTIAL_STORE",
+            ConfigKey::NoUpdateCheck => "SIDEKO_NO_UPDATE_CHECK",
+            ConfigKey::UpdateCheckTtl => "SIDEKO_UPDATE_CHECK_TTL",
+            ConfigKey::Proxy => "SIDEKO_PROXY",
+            ConfigKey::NoProxy => "SIDEKO_NO_PROXY",
+            ConfigKey::CaCerts => "SIDEKO_CA_CERTS",
+            ConfigKey::Timeout => "SIDEKO_TIMEOUT",
+            ConfigKey::LogFile => "SIDEKO_LOG_FILE",
+        }
+    }
+
+    /// short name used to reference the key in `sideko config` commands
+    pub fn name(&self) -> &'static str {
+        match self {
+            ConfigKey::ConfigPath => "config-path",
+            ConfigKey::ApiKey => "api-key",
+            ConfigKey::ApiBaseUrl => "base-url",
+            ConfigKey::Profile => "profile",
+            ConfigKey::CredentialStore => "credential-store",
+            ConfigKey::NoUpdateCheck => "no-update-check",
+            ConfigKey::UpdateCheckTtl => "update-check-ttl",
+            ConfigKey::Proxy => "proxy",
+            ConfigKey::NoProxy => "no-proxy",
+            ConfigKey::CaCerts => "ca-certs",
+            ConfigKey::Timeout => "timeout",
+            ConfigKey::LogFile => "log-file",
+        }
+    }
+
+    /// secrets are stored in the credential store and masked when displayed
+    pub fn is_secret(&self) -> bool {
+        matches!(self, ConfigKey::ApiKey)
+    }
+
+    /// validates a value before it is persisted with `sideko config set`
+    pub fn validate(&self, val: &str) -> CliResult<()> {
+        match self {
+            ConfigKey::Profile => {
+                if !Profile::new(val)?.exists()? {
+                    return Err(CliError::general(format!(
+                        "profile `{val}` does not exist, create it with `sideko config profile add {val}`"
+                    ))
+                    .with_code(ErrorCode::Config));
+                }
+            }
+            ConfigKey::CredentialStore => {
+                CredentialStore::from_str(val)?;
+            }
+            ConfigKey::NoUpdateCheck => {
+                parse_bool(val).ok_or_else(|| {
+                    CliError::general(format!(
+                        "invalid value `{val}` for {}, expected `true` or `false`",
+                        self.name()
+                    ))
+                    .with_code(ErrorCode::Config)
+                })?;
+            }
+            ConfigKey::UpdateCheckTtl => {
+                val.parse::<u64>().map_err(|e| {
+                    CliError::general_debug(
+                        format!(
+                            "invalid value `{val}` for {}, expected a number of seconds",
+                            self.name()
+                        ),
+                        e,
+                    )
+                    .with_code(ErrorCode::Config)
+                })?;
+            }
+            ConfigKey::Proxy => {
+                http::parse_proxy(val)?;
+            }
+            ConfigKey::CaCerts => {
+                http::parse_ca_certs(val)?;
+            }
+            ConfigKey::Timeout => {
+                http::parse_timeout(val)?;
+            }
+            ConfigKey::ConfigPath
+            | ConfigKey::ApiKey
+            | ConfigKey::ApiBaseUrl
+            | ConfigKey::NoProxy
+            | ConfigKey::LogFile => {}
+        }
+
+        Ok(())
+    }
+
+    /// value used when the key is not configured anywhere
+    pub fn default_value(&self) -> CliResult<Option<String>> {
+        let val = match self {
+            ConfigKey::ConfigPath => Some(Profile::active()?.config_path()?.to_string()),
+            ConfigKey::ApiKey => None,
+            ConfigKey::ApiBaseUrl => {
+                Some(sideko_rest_api::environment::Environment::default().to_string())
+            }
+            ConfigKey::Profile => Some(DEFAULT_PROFILE.to_string()),
+            ConfigKey::CredentialStore => Some(CredentialStore::Auto.to_string()),
+            ConfigKey::NoUpdateCheck => Some("false".into()),
+            ConfigKey::UpdateCheckTtl => Some(DEFAULT_UPDATE_CHECK_TTL.to_string()),
+            ConfigKey::Proxy
+            | ConfigKey::NoProxy
+            | ConfigKey::CaCerts
+            | ConfigKey::Timeout
+            | ConfigKey::LogFile => None,
+        };
+
+        Ok(val)
+    }
+
+    /// dotenv file the key is persisted to by `sideko config set`
+    ///
+    /// the active profile is persisted in the default profile's config and the config path
+    /// redirect is persisted in the active profile's config, all other keys are persisted to
+    /// the active config
+    fn dotenv_path(&self) -> CliResult<Utf8PathBuf> {
+        match self {
+            ConfigKey::Profile => Profile::default_profile().config_path(),
+            ConfigKey::ConfigPath => Profile::active()?.config_path(),
+            _ => get_config_path(),
+        }
+    }
+
+    /// resolves the effective value of the key along with where it was loaded from
+    pub fn resolve(&self) -> CliResult<Option<(String, ConfigSource)>> {
+        if let Some(val) = self.get_env() {
+            // dotenv values are loaded into the environment, compare against the loaded files to tell them apart
+            let mut candidates = vec![self.dotenv_path()?, Profile::active()?.config_path()?];
+            candidates.dedup();
+            for cfg_path in candidates {
+                if read_dotenv_value(&cfg_path, self)?.as_ref() == Some(&val) {
+                    return Ok(Some((val, ConfigSource::Dotenv(cfg_path))));
+                }
+            }
+            return Ok(Some((val, ConfigSource::Env)));
+        }
+
+        if self.is_secret() {
+            if let Some((val, backend)) = self.get_profile_credential(&Profile::active()?) {
+                let source = match backend {
+                    CredentialBackend::Keyring => ConfigSource::Keyring,
+                    CredentialBackend::File => ConfigSource::CredentialFile(credentials_file()?),
+                };
+                return Ok(Some((val, source)));
+            }
+        }
+
+        Ok(self
+            .default_value()?
+            .map(|val| (val, ConfigSource::Default)))
+    }
+
+    /// persists the value in the credential store (secrets) or the dotenv of the key
+    pub fn set<S: ToString>(&self, val: S) -> CliResult<()> {
+        if self.is_secret() {
+            self.set_credential(val)
+        } else {
+            self.set_dotenv(&self.dotenv_path()?, val)
+        }
+    }
+
+    /// removes the persisted value from the credential store (secrets) or the dotenv of the key
+    pub fn unset(&self) -> CliResult<()> {
+        if self.is_secret() {
+            self.unset_credential()
+        } else {
+            self.unset_dotenv(&self.dotenv_path()?)
+        }
+    }
+
+    /// reads the content of the dotenv file at the given path
     /// and returns it's lines
-    fn read_dotenv(&self) -> CliResult<Vec<String>> {
-        let cfg_path = get_config_path()?;
+    fn read_dotenv(&self, cfg_path: &Utf8PathBuf) -> CliResult<Vec<String>> {
         let lines= if cfg_path.exists() {
-            let dotenv_string = std::fs::read_to_string(cfg_path.clone()).map_err(|e| {
+            let dotenv_string = std::fs::read_to_string(cfg_path).map_err(|e| {
                 CliError::io_custom(format!("failed loading sideko config file to update {self}: {cfg_path}"), e)
             })?;
             dotenv_string.split("\n").map(String::from).collect()
@@ -27,110 +230,378 @@ impl ConfigKey {
         Ok(lines)
     }
 
+    /// writes the lines back to the dotenv file at the given path
+    fn write_dotenv(&self, cfg_path: &Utf8PathBuf, lines: &[String]) -> CliResult<()> {
+        if let Some(parent) = cfg_path.parent() {
+            if !parent.as_str().is_empty() && !parent.exists() {
+                std::fs::create_dir_all(parent).map_err(|e| {
+                    CliError::io_custom(
+                        format!("failed creating sideko config directory: {parent}"),
+                        e,
+                    )
+                })?;
+            }
+        }
+        std::fs::write(cfg_path, lines.join("\n")).map_err(|e| {
+            CliError::io_custom(
+                format!("failed updating sideko config {self}: {cfg_path}"),
+                e,
+            )
+        })?;
+
+        Ok(())
+    }
+
     /// retrieves config key value from environment variable
     pub fn get_env(&self) -> Option<String> {
         env::var(self.to_string()).ok()
     }
 
-    /// retrieves config key value from native key storage using keyring
-    pub fn get_keyring(&self) -> Option<String> {
-        match  keyring::Entry::new("sideko", &self.to_string()) {
-            Ok(entry) => {
-                match entry.get_password() {
-                    Ok(v) => return Some(v),
-                    Err(e) => {
-                        if !matches!(e, keyring::Error::NoEntry) {
-                            // no entry is a valid error here, other errors are not expected and should be logged
-                            warn!("failed retrieving keyring entry {self}");
-                            debug!("{e:?}");
-                        }
-                    }
-                }
-            }
-            Err(e) =>  {
-                warn!("failed initializing keyring entry {self}");
-                debug!("{e:?}");
-            }
-        
+    /// retrieves secret config key value from the credential store of the active profile
+    pub fn get_credential(&self) -> Option<String> {
+        self.get_profile_credential(&Profile::active().ok()?)
+            .map(|(val, _)| val)
+    }
+
+    /// retrieves secret config key value from the credential store of the given profile
+    /// along with the backend it was found in
+    pub fn get_profile_credential(&self, profile: &Profile) -> Option<(String, CredentialBackend)> {
+        CredentialStore::active().get(&profile.keyring_service(), &self.to_string())
+    }
+
+    /// sets secret config key value in the credential store of the active profile
+    pub fn set_credential<S: ToString>(&self, val: S) -> CliResult<()> {
+        self.set_profile_credential(&Profile::active()?, val)
     }
-    None
+
+    /// sets secret config key value in the credential store of the given profile
+    pub fn set_profile_credential<S: ToString>(&self, profile: &Profile, val: S) -> CliResult<()> {
+        let backend = CredentialStore::active().set(
+            &profile.keyring_service(),
+            &self.to_string(),
+            &val.to_string(),
+        )?;
+
+        debug!("Set credential {self} (profile: {profile}, store: {backend:?})");
+
+        Ok(())
+    }
+
+    /// sets key in the dotenv of the given profile, replacing any existing value
+    pub fn set_profile_env<S: ToString>(&self, profile: &Profile, val: S) -> CliResult<()> {
+        self.set_dotenv(&profile.config_path()?, val)
     }
 
-    /// sets config key value in the native key storage using keyring
-    pub fn set_keyring<S: ToString>(&self, val: S) -> CliResult<()> {
-        let entry = keyring::Entry::new("sideko", &self.to_string())?;
-        entry.set_password(&val.to_string())?;
+    fn set_dotenv<S: ToString>(&self, cfg_path: &Utf8PathBuf, val: S) -> CliResult<()> {
+        let mut dotenv: Vec<String> = self
+            .read_dotenv(cfg_path)?
+            .into_iter()
+            .filter(|l| !l.starts_with(&format!("{self}=")))
+            .collect();
+        dotenv.push(format!("{self}={}", val.to_string()));
+        self.write_dotenv(cfg_path, &dotenv)?;
 
-        debug!("Set keyring entry {self}");
+        debug!("Set dotenv config {self} in {cfg_path}");
 
-        Ok(()) 
+        Ok(())
     }
 
-    /// removes key from dotenv
+    /// removes key from dotenv of the active config
     pub fn unset_env(&self) -> CliResult<()> {
-        let curr_dotenv = self.read_dotenv()?;
-        let new_dotenv: Vec<String> = curr_dotenv.clone().into_iter().filter(|l| !l.starts_with(&format!("{self}="))).collect();
+        self.unset_dotenv(&get_config_path()?)
+    }
+
+    /// removes key from dotenv of the given profile
+    pub fn unset_profile_env(&self, profile: &Profile) -> CliResult<()> {
+        self.unset_dotenv(&profile.config_path()?)
+    }
+
+    fn unset_dotenv(&self, cfg_path: &Utf8PathBuf) -> CliResult<()> {
+        let curr_dotenv = self.read_dotenv(cfg_path)?;
+        let new_dotenv: Vec<String> = curr_dotenv
+            .clone()
+            .into_iter()
+            .filter(|l| !l.starts_with(&format!("{self}=")))
+            .collect();
 
         if new_dotenv.len() < curr_dotenv.len() {
             debug!("Removed dotenv config {self}")
         }
 
-        let cfg_path = get_config_path()?;
-        std::fs::write(&cfg_path, new_dotenv.join("\n")).map_err(|e| {
-            CliError::io_custom(format!("failed updating sideko config {self}: {cfg_path}"), e)
+        self.write_dotenv(cfg_path, &new_dotenv)
+    }
+
+    /// removes key from the credential store of the active profile
+    pub fn unset_credential(&self) -> CliResult<()> {
+        self.unset_profile_credential(&Profile::active()?)
+    }
+
+    /// removes key from the credential store of the given profile
+    pub fn unset_profile_credential(&self, profile: &Profile) -> CliResult<()> {
+        CredentialStore::active().delete(&profile.keyring_service(), &self.to_string())
+    }
+}
+impl Display for ConfigKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.env_var())
+    }
+}
+impl clap::ValueEnum for ConfigKey {
+    fn value_variants<'a>() -> &'a [Self] {
+        &[
+            ConfigKey::Profile,
+            ConfigKey::ApiBaseUrl,
+            ConfigKey::ApiKey,
+            ConfigKey::CredentialStore,
+            ConfigKey::NoUpdateCheck,
+            ConfigKey::UpdateCheckTtl,
+            ConfigKey::Proxy,
+            ConfigKey::NoProxy,
+            ConfigKey::CaCerts,
+            ConfigKey::Timeout,
+            ConfigKey::LogFile,
+            ConfigKey::ConfigPath,
+        ]
+    }
+
+    fn to_possible_value(&self) -> Option<clap::builder::PossibleValue> {
+        // the environment variable name is accepted as well
+        Some(clap::builder::PossibleValue::new(self.name()).alias(self.env_var()))
+    }
+}
+
+/// where the effective value of a config key was loaded from
+#[derive(Debug, Clone)]
+pub enum ConfigSource {
+    Env,
+    Dotenv(Utf8PathBuf),
+    Keyring,
+    CredentialFile(Utf8PathBuf),
+    Default,
+}
+impl Display for ConfigSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConfigSource::Env => write!(f, "env var"),
+            ConfigSource::Dotenv(path) => write!(f, "dotenv ({path})"),
+            ConfigSource::Keyring => write!(f, "keyring"),
+            ConfigSource::CredentialFile(path) => write!(f, "encrypted file ({path})"),
+            ConfigSource::Default => write!(f, "default"),
+        }
+    }
+}
+
+/// parses boolean-like config values (`true`/`false`, `1`/`0`, `yes`/`no`)
+pub(crate) fn parse_bool(val: &str) -> Option<bool> {
+    match val.trim().to_lowercase().as_str() {
+        "true" | "1" | "yes" | "on" => Some(true),
+        "false" | "0" | "no" | "off" | "" => Some(false),
+        _ => None,
+    }
+}
+
+/// masks all but the last 4 characters of a secret
+pub(crate) fn mask_secret(secret: &str) -> String {
+    let chars: Vec<char> = secret.chars().collect();
+    if chars.len() <= 8 {
+        "*".repeat(chars.len())
+    } else {
+        let visible: String = chars[chars.len() - 4..].iter().collect();
+        format!("{}{visible}", "*".repeat(chars.len() - 4))
+    }
+}
+
+/// reads a single key from a dotenv file without loading it into the environment
+fn read_dotenv_value(cfg_path: &Utf8PathBuf, key: &ConfigKey) -> CliResult<Option<String>> {
+    if !cfg_path.is_file() {
+        return Ok(None);
+    }
+
+    let iter = dotenvy::from_path_iter(cfg_path).map_err(|e| {
+        CliError::general_debug(format!("failed loading sideko config: {cfg_path}"), e)
+            .with_code(ErrorCode::Config)
+    })?;
+    for item in iter {
+        let (k, v) = item.map_err(|e| {
+            CliError::general_debug(format!("failed parsing sideko config: {cfg_path}"), e)
+                .with_code(ErrorCode::Config)
         })?;
+        if k == key.to_string() {
+            return Ok(Some(v));
+        }
+    }
 
-        Ok(())
+    Ok(None)
+}
+
+/// A named set of configuration (dotenv file + keyring entries) used to switch
+/// between sideko organizations and environments
+///
+/// The `default` profile uses the `$HOME/.sideko` dotenv and the `sideko` keyring
+/// service, every other profile uses `$HOME/.sideko-profiles/<name>` and the
+/// `sideko:<name>` keyring service
+#[derive(Debug, Clone, PartialEq)]
+pub struct Profile {
+    pub name: String,
+}
+impl Profile {
+    /// builds a profile, validating the name
+    pub fn new(name: &str) -> CliResult<Self> {
+        let pattern = Regex::new(r"^[a-zA-Z0-9_-]+$").expect("invalid profile name regex pattern");
+        if !pattern.is_match(name) {
+            return Err(CliError::general(format!(
+                "invalid profile name `{name}`: only alphanumeric characters, dashes, and underscores are permitted"
+            ))
+            .with_code(ErrorCode::Config));
+        }
+
+        Ok(Self { name: name.into() })
     }
 
-    pub fn unset_keyring(&self) ->CliResult<()> {
-        let entry = keyring::Entry::new("sideko", &self.to_string())?;
-        match entry.delete_credential() {
-            Ok(_) => debug!("removed keyring entry {self}"),
-            Err(e) => {
-                if !matches!(e, keyring::Error::NoEntry) {
-                    // genuine error has occurred
-                    return Err(e.into())
-                }
-            },
+    pub fn default_profile() -> Self {
+        Self {
+            name: DEFAULT_PROFILE.into(),
         }
+    }
 
-        Ok(())
+    /// the profile selected via `--profile`/`SIDEKO_PROFILE`,
+    /// falling back to the default profile
+    ///
+    /// returns an Err if the name is invalid as it is used in config paths and keyring
+    /// service names
+    pub fn active() -> CliResult<Self> {
+        match ConfigKey::Profile.get_env() {
+            Some(name) if !name.is_empty() => Self::new(&name),
+            _ => Ok(Self::default_profile()),
+        }
     }
 
+    pub fn is_default(&self) -> bool {
+        self.name == DEFAULT_PROFILE
+    }
+
+    pub fn is_active(&self) -> bool {
+        Self::active().is_ok_and(|active| self == &active)
+    }
 
+    /// service name used for keyring entries of this profile
+    pub fn keyring_service(&self) -> String {
+        if self.is_default() {
+            "sideko".into()
+        } else {
+            format!("sideko:{}", &self.name)
+        }
+    }
+
+    /// dotenv path holding the config of this profile
+    pub fn config_path(&self) -> CliResult<Utf8PathBuf> {
+        if self.is_default() {
+            get_default_config_path()
+        } else {
+            Ok(get_profiles_dir()?.join(&self.name))
+        }
+    }
+
+    /// the default profile always exists, others exist once their dotenv has been created
+    pub fn exists(&self) -> CliResult<bool> {
+        Ok(self.is_default() || self.config_path()?.is_file())
+    }
+
+    /// lists the default profile followed by all profiles found in the profiles directory
+    pub fn list() -> CliResult<Vec<Self>> {
+        let mut profiles = vec![Self::default_profile()];
+
+        let profiles_dir = get_profiles_dir()?;
+        if profiles_dir.is_dir() {
+            let entries = profiles_dir.read_dir_utf8().map_err(|e| {
+                CliError::io_custom(
+                    format!("failed reading profiles directory: {profiles_dir}"),
+                    e,
+                )
+            })?;
+            let mut names = vec![];
+            for entry in entries.flatten() {
+                if entry.path().is_file() {
+                    if let Ok(profile) = Self::new(entry.file_name()) {
+                        names.push(profile);
+                    }
+                }
+            }
+            names.sort_by(|a, b| a.name.cmp(&b.name));
+            profiles.extend(names);
+        }
+
+        Ok(profiles)
+    }
+
+    /// reads a single key from this profile's dotenv without loading it into the environment
+    pub fn read_env(&self, key: &ConfigKey) -> CliResult<Option<String>> {
+        read_dotenv_value(&self.config_path()?, key)
+    }
 }
-impl Display for ConfigKey {
+impl Display for Profile {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let env_var = match self {
-            ConfigKey::ApiKey => "SIDEKO_API_KEY",
-            ConfigKey::ApiBaseUrl => "SIDEKO_BASE_URL",
-            ConfigKey::ConfigPath => "SIDEKO_CONFIG_PATH",
-        };
-
-        write!(f, "{env_var}")
+        write!(f, "{}", &self.name)
     }
 }
 
 pub(crate) fn load() -> CliResult<()> {
-    let cfg_path = get_config_path()?;
+    // the active profile can be persisted in the default config via `sideko config profile use`
+    if ConfigKey::Profile.get_env().is_none() {
+        if let Some(name) = Profile::default_profile().read_env(&ConfigKey::Profile)? {
+            match Profile::new(&name) {
+                Ok(profile) if profile.exists()? => {
+                    env::set_var(ConfigKey::Profile.to_string(), &profile.name);
+                }
+                _ => warn!(
+                    "active profile `{name}` no longer exists, using the `{DEFAULT_PROFILE}` profile"
+                ),
+            }
+        }
+    }
+
+    let profile = Profile::active()?;
+    if !profile.exists()? {
+        return Err(CliError::general(format!(
+            "profile `{profile}` does not exist, create it with `sideko config profile add {profile}`"
+        ))
+        .with_code(ErrorCode::Config));
+    }
+    debug!("using profile: {profile}");
+
+    let config_path_unset = ConfigKey::ConfigPath.get_env().is_none();
+    load_dotenv(&get_config_path()?)?;
+
+    // the profile config may redirect to another config via `sideko config set config-path`
+    if config_path_unset && ConfigKey::ConfigPath.get_env().is_some() {
+        load_dotenv(&get_config_path()?)?;
+    }
+
+    Ok(())
+}
+
+fn load_dotenv(cfg_path: &Utf8PathBuf) -> CliResult<()> {
     if cfg_path.exists() {
-        dotenvy::from_path(&cfg_path).map_err(|e| CliError::general_debug(format!("failed loading sideko config: {cfg_path}"), e))?;
+        dotenvy::from_path(cfg_path).map_err(|e| {
+            CliError::general_debug(format!("failed loading sideko config: {cfg_path}"), e)
+                .with_code(ErrorCode::Config)
+        })?;
         debug!("loaded config: {cfg_path}");
     }
+
     Ok(())
 }
 
 /// first tries retrieving the api key from the ConfigKey::ApiKey env var,
-/// if that is not set then it will try to retrieve it from keyring
+/// if that is not set then it will try to retrieve it from the credential store
 pub(crate) fn get_api_key() -> Option<String> {
     if let Some(env_key) = ConfigKey::ApiKey.get_env() {
         debug!("Retrieved API key from env");
         Some(env_key)
     }
-    else if let Some(keyring_key) = ConfigKey::ApiKey.get_keyring() {
-        debug!("Retrieved API key from keyring");
-        Some(keyring_key)
+    else if let Some(stored_key) = ConfigKey::ApiKey.get_credential() {
+        debug!("Retrieved API key from credential store");
+        Some(stored_key)
     } else {
         None
     }
@@ -138,7 +609,7 @@ pub(crate) fn get_api_key() -> Option<String> {
 
 
 /// retrieves the config path from user-set ConfigKey::ConfigPath,
-/// defaulting to $HOME/.sideko if not set
+/// defaulting to the config path of the active profile if not set
 pub(crate) fn get_config_path() -> CliResult<Utf8PathBuf> {
     if let Some(p) = ConfigKey::ConfigPath.get_env() {
         let path = Utf8PathBuf::from_str(&p).map_err(|e| {
@@ -149,28 +620,53 @@ pub(crate) fn get_config_path() -> CliResult<Utf8PathBuf> {
                 ),
                 format!("{e:?}"),
             )
+            .with_code(ErrorCode::Config)
         })?;
         Ok(path)
     } else {
-        get_default_config_path()
+        Profile::active()?.config_path()
     }
 
 }
-pub(crate) fn get_default_config_path() -> CliResult<Utf8PathBuf> {
-    let home = env::var("HOME")
-            .map_err(|_| CliError::general("Unable to build default config path: $HOME is not set"))?;
-        let mut default_path = Utf8PathBuf::from_str(&home).map_err(|e| {
-            CliError::general_debug(
-                format!(
+
+fn get_home_dir() -> CliResult<Utf8PathBuf> {
+    let home = env::var("HOME").map_err(|_| {
+        CliError::general("Unable to build default config path: $HOME is not set")
+            .with_code(ErrorCode::Config)
+    })?;
+    Utf8PathBuf::from_str(&home).map_err(|e| {
+        CliError::general_debug(
+            format!(
                 "Unable to build default config path: $HOME is set to an ill-formatted path: {home}"
-    
             ),
-                format!("{e:?}"),
-            )
-        })?;
-        default_path.push(".sideko");
-    
-        Ok(default_path)
+            format!("{e:?}"),
+        )
+        .with_code(ErrorCode::Config)
+    })
+}
+
+pub(crate) fn get_default_config_path() -> CliResult<Utf8PathBuf> {
+    Ok(get_home_dir()?.join(".sideko"))
+}
+
+/// directory holding the dotenv files of all non-default profiles
+pub(crate) fn get_profiles_dir() -> CliResult<Utf8PathBuf> {
+    Ok(get_home_dir()?.join(".sideko-profiles"))
+}
+
+/// file caching the result of the last update check
+pub(crate) fn get_update_cache_path() -> CliResult<Utf8PathBuf> {
+    Ok(get_home_dir()?.join(".sideko-update-check.json"))
+}
+
+/// file listing the api versions archived on this machine
+pub(crate) fn get_archive_path() -> CliResult<Utf8PathBuf> {
+    Ok(get_home_dir()?.join(".sideko-archived-versions.json"))
+}
+
+/// directory holding the encrypted credential file store
+pub(crate) fn get_credentials_dir() -> CliResult<Utf8PathBuf> {
+    Ok(get_home_dir()?.join(".sideko-credentials"))
 }
 
 /// retrieves API base url from user-set ConfigKey::ApiBaseUrl,
@@ -0,0 +1,463 @@
+use std::{collections::BTreeMap, fmt::Display, str::FromStr};
+
+use camino::Utf8PathBuf;
+use log::{debug, warn};
+use ring::{
+    aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_256_GCM, NONCE_LEN},
+    rand::{SecureRandom, SystemRandom},
+};
+
+use crate::result::{CliError, CliResult, ErrorCode};
+
+use super::config::{self, ConfigKey};
+
+/// where secret config values (e.g. the api key) are persisted
+///
+/// `auto` prefers the os keyring and falls back to the encrypted file store
+/// when no keyring is available (e.g. headless linux ci runners & containers)
+#[derive(Debug, Clone, PartialEq)]
+pub enum CredentialStore {
+    Auto,
+    Keyring,
+    File,
+}
+impl CredentialStore {
+    pub fn all() -> Vec<CredentialStore> {
+        vec![
+            CredentialStore::Auto,
+            CredentialStore::Keyring,
+            CredentialStore::File,
+        ]
+    }
+
+    /// store selected via `ConfigKey::CredentialStore`, defaulting to `auto`
+    pub fn active() -> Self {
+        match ConfigKey::CredentialStore.get_env() {
+            Some(val) => Self::from_str(&val).unwrap_or_else(|e| {
+                e.log();
+                warn!("falling back to the `{}` credential store", Self::Auto);
+                Self::Auto
+            }),
+            None => Self::Auto,
+        }
+    }
+
+    /// retrieves the secret along with the backend it was found in
+    pub fn get(&self, service: &str, account: &str) -> Option<(String, CredentialBackend)> {
+        match self {
+            CredentialStore::Keyring => keyring_get(service, account),
+            CredentialStore::File => file_get(service, account),
+            CredentialStore::Auto => {
+                keyring_get(service, account).or_else(|| file_get(service, account))
+            }
+        }
+    }
+
+    /// persists the secret, returning the backend it was stored in
+    pub fn set(&self, service: &str, account: &str, val: &str) -> CliResult<CredentialBackend> {
+        match self {
+            CredentialStore::Keyring => {
+                keyring_set(service, account, val)?;
+                Ok(CredentialBackend::Keyring)
+            }
+            CredentialStore::File => {
+                EncryptedFileStore::open()?.set(service, account, val)?;
+                Ok(CredentialBackend::File)
+            }
+            CredentialStore::Auto => match keyring_set(service, account, val) {
+                Ok(_) => {
+                    // a previous fallback may have left a stale secret behind
+                    if let Err(e) = file_delete(service, account) {
+                        debug!("failed removing stale credential file entry: {e:?}");
+                    }
+                    Ok(CredentialBackend::Keyring)
+                }
+                Err(e) => {
+                    warn!("os keyring is unavailable, storing {account} in the encrypted credential file");
+                    debug!("{e:?}");
+                    EncryptedFileStore::open()?.set(service, account, val)?;
+                    Ok(CredentialBackend::File)
+                }
+            },
+        }
+    }
+
+    /// removes the secret, missing entries are not an error
+    pub fn delete(&self, service: &str, account: &str) -> CliResult<()> {
+        match self {
+            CredentialStore::Keyring => keyring_delete(service, account),
+            CredentialStore::File => file_delete(service, account),
+            CredentialStore::Auto => {
+                // both stores are cleared before reporting, a failing one must not
+                // leave the secret behind in the other
+                let file_res = file_delete(service, account);
+                if let Err(e) = keyring_delete(service, account) {
+                    // the keyring may simply be unavailable on this machine
+                    debug!("failed removing {account} from the os keyring: {e:?}");
+                }
+                file_res
+            }
+        }
+    }
+}
+impl FromStr for CredentialStore {
+    type Err = CliError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::all()
+            .into_iter()
+            .find(|store| store.to_string() == s)
+            .ok_or_else(|| {
+                CliError::general(format!(
+                    "invalid credential store `{s}`, expected one of: {}",
+                    Self::all()
+                        .iter()
+                        .map(|s| s.to_string())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ))
+                .with_code(ErrorCode::Config)
+            })
+    }
+}
+impl Display for CredentialStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let val = match self {
+            CredentialStore::Auto => "auto",
+            CredentialStore::Keyring => "keyring",
+            CredentialStore::File => "file",
+        };
+        write!(f, "{val}")
+    }
+}
+
+/// backend a secret was read from or written to
+#[derive(Debug, Clone, PartialEq)]
+pub enum CredentialBackend {
+    Keyring,
+    File,
+}
+
+// ------------ OS KEYRING ------------
+
+fn keyring_get(service: &str, account: &str) -> Option<(String, CredentialBackend)> {
+    match keyring::Entry::new(service, account) {
+        Ok(entry) => {
+            match entry.get_password() {
+                Ok(v) => return Some((v, CredentialBackend::Keyring)),
+                Err(e) => {
+                    if !matches!(e, keyring::Error::NoEntry) {
+                        // no entry is a valid error here, other errors mean the keyring is unusable
+                        debug!("failed retrieving keyring entry {account}: {e:?}");
+                    }
+                }
+            }
+        }
+        Err(e) => {
+            debug!("failed initializing keyring entry {account}: {e:?}");
+        }
+    }
+    None
+}
+
+fn keyring_set(service: &str, account: &str, val: &str) -> CliResult<()> {
+    let entry = keyring::Entry::new(service, account)?;
+    entry.set_password(val)?;
+    debug!("set keyring entry {account} ({service})");
+
+    Ok(())
+}
+
+fn keyring_delete(service: &str, account: &str) -> CliResult<()> {
+    let entry = keyring::Entry::new(service, account)?;
+    match entry.delete_credential() {
+        Ok(_) => debug!("removed keyring entry {account} ({service})"),
+        Err(e) => {
+            if !matches!(e, keyring::Error::NoEntry) {
+                // genuine error has occurred
+                return Err(e.into());
+            }
+        }
+    }
+
+    Ok(())
+}
+
+// ------------ ENCRYPTED FILE ------------
+
+fn file_get(service: &str, account: &str) -> Option<(String, CredentialBackend)> {
+    let store = match EncryptedFileStore::open() {
+        Ok(s) => s,
+        Err(e) => {
+            debug!("failed opening credential file store: {e:?}");
+            return None;
+        }
+    };
+    match store.get(service, account) {
+        Ok(val) => val.map(|v| (v, CredentialBackend::File)),
+        Err(e) => {
+            warn!("failed reading {account} from the encrypted credential file");
+            debug!("{e:?}");
+            None
+        }
+    }
+}
+
+fn file_delete(service: &str, account: &str) -> CliResult<()> {
+    EncryptedFileStore::open()?.delete(service, account)
+}
+
+/// secrets encrypted with AES-256-GCM using a randomly generated key,
+/// both the key and the secrets are only readable by the current user
+///
+/// the key is stored next to the secrets, so this only guards against casual reads
+/// (e.g. a credentials file shared by accident) and is not encryption at rest
+///
+/// layout of `$HOME/.sideko-credentials`:
+///   - `key`: 32 byte encryption key
+///   - `credentials.json`: `{ service: { account: hex(nonce + ciphertext) } }`
+struct EncryptedFileStore {
+    dir: Utf8PathBuf,
+}
+type Entries = BTreeMap<String, BTreeMap<String, String>>;
+
+impl EncryptedFileStore {
+    fn open() -> CliResult<Self> {
+        Ok(Self {
+            dir: config::get_credentials_dir()?,
+        })
+    }
+
+    fn key_path(&self) -> Utf8PathBuf {
+        self.dir.join("key")
+    }
+
+    fn credentials_path(&self) -> Utf8PathBuf {
+        self.dir.join("credentials.json")
+    }
+
+    fn get(&self, service: &str, account: &str) -> CliResult<Option<String>> {
+        let entries = self.read_entries()?;
+        let Some(encrypted) = entries.get(service).and_then(|s| s.get(account)) else {
+            return Ok(None);
+        };
+
+        let mut sealed = hex_decode(encrypted).ok_or_else(|| {
+            CliError::general(format!("malformed credential entry {account} ({service})"))
+                .with_code(ErrorCode::Credentials)
+        })?;
+        if sealed.len() < NONCE_LEN {
+            return Err(CliError::general(format!(
+                "malformed credential entry {account} ({service})"
+            ))
+            .with_code(ErrorCode::Credentials));
+        }
+        let ciphertext = sealed.split_off(NONCE_LEN);
+        let nonce = Nonce::try_assume_unique_for_key(&sealed).map_err(|_| {
+            CliError::general("malformed credential nonce").with_code(ErrorCode::Credentials)
+        })?;
+
+        let mut in_out = ciphertext;
+        let plaintext = self
+            .cipher(false)?
+            .open_in_place(nonce, aad(service, account), &mut in_out)
+            .map_err(|_| {
+                CliError::general(format!(
+                    "failed decrypting credential {account} ({service}), the key file may have changed"
+                ))
+                .with_code(ErrorCode::Credentials)
+            })?;
+
+        String::from_utf8(plaintext.to_vec())
+            .map(Some)
+            .map_err(|e| {
+                CliError::general_debug("credential is not valid utf-8", format!("{e:?}"))
+                    .with_code(ErrorCode::Credentials)
+            })
+    }
+
+    fn set(&self, service: &str, account: &str, val: &str) -> CliResult<()> {
+        let mut nonce_bytes = [0u8; NONCE_LEN];
+        SystemRandom::new().fill(&mut nonce_bytes).map_err(|_| {
+            CliError::general("failed generating credential nonce")
+                .with_code(ErrorCode::Credentials)
+        })?;
+
+        let mut in_out = val.as_bytes().to_vec();
+        self.cipher(true)?
+            .seal_in_place_append_tag(
+                Nonce::assume_unique_for_key(nonce_bytes),
+                aad(service, account),
+                &mut in_out,
+            )
+            .map_err(|_| {
+                CliError::general(format!("failed encrypting credential {account}"))
+                    .with_code(ErrorCode::Credentials)
+            })?;
+
+        let mut sealed = nonce_bytes.to_vec();
+        sealed.extend(in_out);
+
+        let mut entries = self.read_entries()?;
+        entries
+            .entry(service.into())
+            .or_default()
+            .insert(account.into(), hex_encode(&sealed));
+        self.write_entries(&entries)?;
+        debug!("set credential file entry {account} ({service})");
+
+        Ok(())
+    }
+
+    fn delete(&self, service: &str, account: &str) -> CliResult<()> {
+        let mut entries = self.read_entries()?;
+        let removed = entries
+            .get_mut(service)
+            .and_then(|s| s.remove(account))
+            .is_some();
+        if removed {
+            entries.retain(|_, accounts| !accounts.is_empty());
+            self.write_entries(&entries)?;
+            debug!("removed credential file entry {account} ({service})");
+        }
+
+        Ok(())
+    }
+
+    fn read_entries(&self) -> CliResult<Entries> {
+        let path = self.credentials_path();
+        if !path.is_file() {
+            return Ok(Entries::new());
+        }
+
+        let content = std::fs::read_to_string(&path)
+            .map_err(|e| CliError::io_custom(format!("failed reading credentials: {path}"), e))?;
+        serde_json::from_str(&content).map_err(|e| {
+            CliError::general_debug(
+                format!("failed parsing credentials: {path}"),
+                format!("{e:?}"),
+            )
+            .with_code(ErrorCode::Credentials)
+        })
+    }
+
+    fn write_entries(&self, entries: &Entries) -> CliResult<()> {
+        let content = serde_json::to_string_pretty(entries).map_err(|e| {
+            CliError::general_debug("failed serializing credentials", format!("{e:?}"))
+                .with_code(ErrorCode::Credentials)
+        })?;
+        self.write_private(&self.credentials_path(), content.as_bytes())
+    }
+
+    /// loads the encryption key, generating it if it does not exist yet and there are no
+    /// entries encrypted with a previous key
+    fn cipher(&self, create: bool) -> CliResult<LessSafeKey> {
+        let path = self.key_path();
+        let key_bytes = if path.is_file() {
+            std::fs::read(&path).map_err(|e| {
+                CliError::io_custom(format!("failed reading credential key: {path}"), e)
+            })?
+        } else if create {
+            // a new key cannot decrypt the existing entries, they would be lost silently
+            if !self.read_entries()?.is_empty() {
+                return Err(CliError::general(format!(
+                    "credential key {path} is missing and the stored credentials can no longer be decrypted, delete {} and log in again",
+                    self.credentials_path()
+                ))
+                .with_code(ErrorCode::Credentials));
+            }
+            let mut key_bytes = vec![0u8; AES_256_GCM.key_len()];
+            SystemRandom::new().fill(&mut key_bytes).map_err(|_| {
+                CliError::general("failed generating credential key")
+                    .with_code(ErrorCode::Credentials)
+            })?;
+            self.write_private(&path, &key_bytes)?;
+            debug!("generated credential key: {path}");
+            key_bytes
+        } else {
+            return Err(
+                CliError::general(format!("credential key does not exist: {path}"))
+                    .with_code(ErrorCode::Credentials),
+            );
+        };
+
+        let key = UnboundKey::new(&AES_256_GCM, &key_bytes).map_err(|_| {
+            CliError::general(format!("invalid credential key: {path}"))
+                .with_code(ErrorCode::Credentials)
+        })?;
+
+        Ok(LessSafeKey::new(key))
+    }
+
+    /// writes the file so that only the current user can read it
+    fn write_private(&self, path: &Utf8PathBuf, content: &[u8]) -> CliResult<()> {
+        if !self.dir.exists() {
+            std::fs::create_dir_all(&self.dir).map_err(|e| {
+                CliError::io_custom(
+                    format!("failed creating credentials directory: {}", &self.dir),
+                    e,
+                )
+            })?;
+            set_permissions(&self.dir, 0o700)?;
+        }
+
+        write_private_file(path, content)
+    }
+}
+
+/// writes a file only readable & writable by the current user (0600 on unix)
+pub(crate) fn write_private_file(path: &Utf8PathBuf, content: &[u8]) -> CliResult<()> {
+    let mut opts = std::fs::OpenOptions::new();
+    opts.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        opts.mode(0o600);
+    }
+    let mut file = opts
+        .open(path)
+        .map_err(|e| CliError::io_custom(format!("failed opening {path}"), e))?;
+    // the file may have been created with broader permissions before
+    set_permissions(path, 0o600)?;
+
+    std::io::Write::write_all(&mut file, content)
+        .map_err(|e| CliError::io_custom(format!("failed writing {path}"), e))
+}
+
+/// location of the encrypted credentials file
+pub(crate) fn credentials_file() -> CliResult<Utf8PathBuf> {
+    Ok(EncryptedFileStore::open()?.credentials_path())
+}
+
+#[cfg(unix)]
+fn set_permissions(path: &Utf8PathBuf, mode: u32) -> CliResult<()> {
+    use std::os::unix::fs::PermissionsExt;
+    std::fs::set_permissions(path, std::fs::Permissions::from_mode(mode))
+        .map_err(|e| CliError::io_custom(format!("failed restricting permissions of {path}"), e))
+}
+
+#[cfg(not(unix))]
+fn set_permissions(_path: &Utf8PathBuf, _mode: u32) -> CliResult<()> {
+    Ok(())
+}
+
+/// binds the ciphertext to its entry so entries cannot be swapped
+fn aad(service: &str, account: &str) -> Aad<Vec<u8>> {
+    Aad::from(format!("{service}/{account}").into_bytes())
+}
+
+fn hex_encode(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+// `is_multiple_of` needs rust 1.87, newer than the toolchains the cli builds with
+#[allow(clippy::manual_is_multiple_of)]
+fn hex_decode(s: &str) -> Option<Vec<u8>> {
+    if s.len() % 2 != 0 {
+        return None;
+    }
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(s.get(i..i + 2)?, 16).ok())
+        .collect()
+}
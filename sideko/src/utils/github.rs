@@ -0,0 +1,34 @@
+use sideko_rest_api::models::{LintResult, LintSeverityEnum};
+
+/// github actions workflow command annotating the linted file with a lint result, e.g.
+/// `::error file=openapi.yaml,line=12,col=3,endLine=12,endColumn=9,title=operation-id::missing operationId`
+pub fn lint_annotation(uri: &str, result: &LintResult) -> String {
+    let command = match result.severity {
+        LintSeverityEnum::Error => "error",
+        LintSeverityEnum::Warn => "warning",
+        LintSeverityEnum::Info | LintSeverityEnum::Unknown => "notice",
+    };
+    let loc = &result.location;
+    format!(
+        "::{command} file={file},line={line},col={col},endLine={end_line},endColumn={end_col},title={title}::{message}",
+        file = escape_property(uri.strip_prefix("./").unwrap_or(uri)),
+        line = loc.start_line.max(1),
+        col = loc.start_column.max(1),
+        end_line = loc.end_line.max(loc.start_line.max(1)),
+        end_col = loc.end_column.max(1),
+        title = escape_property(&format!("{} ({})", &result.rule, &result.category)),
+        message = escape_data(&format!("{} ({})", &result.message, &result.how_to_fix)),
+    )
+}
+
+/// escapes the message of a workflow command
+fn escape_data(text: &str) -> String {
+    text.replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+/// escapes a property of a workflow command, e.g. `file` or `title`
+fn escape_property(text: &str) -> String {
+    escape_data(text).replace(':', "%3A").replace(',', "%2C")
+}
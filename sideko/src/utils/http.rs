@@ -0,0 +1,216 @@
+use std::{sync::OnceLock, time::Duration};
+
+use camino::Utf8PathBuf;
+use log::debug;
+
+use crate::result::{CliError, CliResult, ErrorCode};
+
+use super::config::ConfigKey;
+
+static CLIENT: OnceLock<reqwest::Client> = OnceLock::new();
+
+/// proxy environment variables, in the order reqwest picks them up
+const PROXY_ENV_VARS: &[(&str, &str)] = &[
+    ("https", "HTTPS_PROXY"),
+    ("https", "https_proxy"),
+    ("http", "HTTP_PROXY"),
+    ("http", "http_proxy"),
+    ("all", "ALL_PROXY"),
+    ("all", "all_proxy"),
+];
+
+/// network settings loaded from the config
+#[derive(Debug, Default)]
+pub(crate) struct HttpSettings {
+    /// proxy for all requests, defaults to the `HTTPS_PROXY`/`HTTP_PROXY` environment variables
+    pub proxy: Option<String>,
+    /// comma separated hosts that bypass the proxy
+    pub no_proxy: Option<String>,
+    /// additional trusted root certificates (pem or der)
+    pub ca_certs: Vec<Utf8PathBuf>,
+    /// timeout of a single request
+    pub timeout: Option<Duration>,
+}
+
+impl HttpSettings {
+    pub(crate) fn from_env() -> CliResult<Self> {
+        Ok(Self {
+            proxy: non_empty_env(&ConfigKey::Proxy),
+            no_proxy: non_empty_env(&ConfigKey::NoProxy),
+            ca_certs: non_empty_env(&ConfigKey::CaCerts)
+                .map(|val| parse_ca_certs(&val))
+                .transpose()?
+                .unwrap_or_default(),
+            timeout: non_empty_env(&ConfigKey::Timeout)
+                .map(|val| parse_timeout(&val))
+                .transpose()?,
+        })
+    }
+
+    /// builds the client used for every request to the sideko api
+    pub(crate) fn build_client(&self) -> CliResult<reqwest::Client> {
+        let mut builder = reqwest::Client::builder();
+
+        if let Some(proxy) = &self.proxy {
+            let mut proxy = parse_proxy(proxy)?;
+            if let Some(no_proxy) = &self.no_proxy {
+                proxy = proxy.no_proxy(reqwest::NoProxy::from_string(no_proxy));
+            }
+            builder = builder.proxy(proxy);
+        } else if let Some(no_proxy) = &self.no_proxy {
+            // reqwest only reads exclusions from `NO_PROXY`, so proxies picked up from
+            // the environment are configured explicitly to apply them
+            for proxy in env_proxies()? {
+                builder = builder.proxy(proxy.no_proxy(reqwest::NoProxy::from_string(no_proxy)));
+            }
+        }
+
+        for path in &self.ca_certs {
+            for cert in read_certificates(path)? {
+                builder = builder.add_root_certificate(cert);
+            }
+        }
+
+        if let Some(timeout) = self.timeout {
+            builder = builder.timeout(timeout);
+        }
+
+        builder.build().map_err(|e| {
+            CliError::general_debug("failed building http client", format!("{e:?}"))
+                .with_code(ErrorCode::Config)
+        })
+    }
+}
+
+fn non_empty_env(key: &ConfigKey) -> Option<String> {
+    key.get_env().filter(|val| !val.trim().is_empty())
+}
+
+/// proxies configured by the environment variables, the first variable set per scheme wins
+fn env_proxies() -> CliResult<Vec<reqwest::Proxy>> {
+    let mut schemes = vec![];
+    let mut proxies = vec![];
+    for (scheme, var) in PROXY_ENV_VARS {
+        let Some(val) = std::env::var(var).ok().filter(|v| !v.trim().is_empty()) else {
+            continue;
+        };
+        if schemes.contains(scheme) {
+            continue;
+        }
+        schemes.push(*scheme);
+
+        let proxy = match *scheme {
+            "https" => reqwest::Proxy::https(&val),
+            "http" => reqwest::Proxy::http(&val),
+            _ => reqwest::Proxy::all(&val),
+        };
+        proxies.push(proxy.map_err(|e| {
+            CliError::general_debug(
+                format!("invalid proxy url in {var}: `{val}`"),
+                format!("{e:?}"),
+            )
+            .with_code(ErrorCode::Config)
+        })?);
+    }
+
+    Ok(proxies)
+}
+
+/// validates the proxy url
+pub(crate) fn parse_proxy(val: &str) -> CliResult<reqwest::Proxy> {
+    reqwest::Proxy::all(val).map_err(|e| {
+        CliError::general_debug(format!("invalid proxy url `{val}`"), format!("{e:?}"))
+            .with_code(ErrorCode::Config)
+    })
+}
+
+/// splits the comma separated list of certificate paths, validating each file
+pub(crate) fn parse_ca_certs(val: &str) -> CliResult<Vec<Utf8PathBuf>> {
+    let paths: Vec<Utf8PathBuf> = val
+        .split(',')
+        .map(str::trim)
+        .filter(|p| !p.is_empty())
+        .map(Utf8PathBuf::from)
+        .collect();
+    for path in &paths {
+        read_certificates(path)?;
+    }
+
+    Ok(paths)
+}
+
+/// parses the timeout in seconds
+pub(crate) fn parse_timeout(val: &str) -> CliResult<Duration> {
+    match val.trim().parse::<u64>() {
+        Ok(secs) if secs > 0 => Ok(Duration::from_secs(secs)),
+        _ => Err(CliError::general(format!(
+            "invalid timeout `{val}`, expected a positive number of seconds"
+        ))
+        .with_code(ErrorCode::Config)),
+    }
+}
+
+/// reads all certificates of a pem bundle or a single der certificate
+fn read_certificates(path: &Utf8PathBuf) -> CliResult<Vec<reqwest::Certificate>> {
+    let content = std::fs::read(path)
+        .map_err(|e| CliError::io_custom(format!("failed reading ca certificate: {path}"), e))?;
+    let invalid = |e: reqwest::Error| {
+        CliError::general_debug(format!("invalid ca certificate: {path}"), format!("{e:?}"))
+            .with_code(ErrorCode::Config)
+    };
+
+    let pem_marker = "-----BEGIN CERTIFICATE-----";
+    let text = String::from_utf8_lossy(&content);
+    if !text.contains(pem_marker) {
+        return Ok(vec![
+            reqwest::Certificate::from_der(&content).map_err(invalid)?
+        ]);
+    }
+
+    text.match_indices(pem_marker)
+        .map(|(start, _)| {
+            let block = &text[start..];
+            let end_marker = "-----END CERTIFICATE-----";
+            let end = block
+                .find(end_marker)
+                .map(|i| i + end_marker.len())
+                .unwrap_or(block.len());
+            reqwest::Certificate::from_pem(&block.as_bytes()[..end]).map_err(invalid)
+        })
+        .collect()
+}
+
+/// includes the sources of the error, reqwest errors alone are rarely actionable
+/// (e.g. "error sending request" without the underlying certificate error)
+pub(crate) fn error_chain(err: &dyn std::error::Error) -> String {
+    let mut msg = err.to_string();
+    let mut source = err.source();
+    while let Some(e) = source {
+        let e_msg = e.to_string();
+        if !msg.contains(&e_msg) {
+            msg.push_str(&format!(": {e_msg}"));
+        }
+        source = e.source();
+    }
+
+    msg
+}
+
+/// builds the shared client from the network settings, must be called after the config
+/// is loaded
+pub(crate) fn init() -> CliResult<()> {
+    if CLIENT.get().is_some() {
+        return Ok(());
+    }
+
+    let settings = HttpSettings::from_env()?;
+    debug!("http settings: {settings:?}");
+    let _ = CLIENT.set(settings.build_client()?);
+
+    Ok(())
+}
+
+/// client sideko clients are built with, `None` until initialized
+pub(crate) fn client() -> Option<reqwest::Client> {
+    CLIENT.get().cloned()
+}
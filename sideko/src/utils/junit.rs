@@ -0,0 +1,95 @@
+use std::fmt::Write;
+
+use sideko_rest_api::models::{LintResult, LintSeverityEnum};
+
+/// junit xml report of lint results, one test suite for the linted file with a test
+/// case per rule that failed if the rule found errors
+///
+/// warnings and info of a rule are listed in the test case output
+pub fn lint_report(uri: &str, results: &[LintResult]) -> String {
+    let uri = uri.strip_prefix("./").unwrap_or(uri);
+    let mut rules: Vec<(&str, Vec<&LintResult>)> = vec![];
+    for result in results {
+        match rules.iter_mut().find(|(rule, _)| *rule == result.rule) {
+            Some((_, rule_results)) => rule_results.push(result),
+            None => rules.push((&result.rule, vec![result])),
+        }
+    }
+    let failures = rules
+        .iter()
+        .filter(|(_, rule_results)| rule_results.iter().any(|r| is_error(r)))
+        .count();
+
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    let _ = writeln!(
+        xml,
+        "<testsuites name=\"sideko api lint\" tests=\"{tests}\" failures=\"{failures}\">",
+        tests = rules.len()
+    );
+    let _ = writeln!(
+        xml,
+        "  <testsuite name=\"{uri}\" tests=\"{tests}\" failures=\"{failures}\" errors=\"0\" skipped=\"0\">",
+        uri = escape(uri),
+        tests = rules.len()
+    );
+    for (rule, rule_results) in &rules {
+        let _ = writeln!(
+            xml,
+            "    <testcase name=\"{rule}\" classname=\"{uri}\">",
+            rule = escape(rule),
+            uri = escape(uri)
+        );
+        let (errors, others): (Vec<&LintResult>, Vec<&LintResult>) =
+            rule_results.iter().copied().partition(|r| is_error(r));
+        if !errors.is_empty() {
+            let _ = writeln!(
+                xml,
+                "      <failure type=\"error\" message=\"{count} lint errors\">{details}</failure>",
+                count = errors.len(),
+                details = escape(&details(uri, &errors))
+            );
+        }
+        if !others.is_empty() {
+            let _ = writeln!(
+                xml,
+                "      <system-out>{}</system-out>",
+                escape(&details(uri, &others))
+            );
+        }
+        xml.push_str("    </testcase>\n");
+    }
+    xml.push_str("  </testsuite>\n</testsuites>\n");
+
+    xml
+}
+
+/// a line per result e.g. `openapi.yaml:12:3 [error] paths./pets.get: missing operationId`
+fn details(uri: &str, results: &[&LintResult]) -> String {
+    results
+        .iter()
+        .map(|r| {
+            format!(
+                "{uri}:{line}:{col} [{severity}] {path}: {message} ({how_to_fix})",
+                line = r.location.start_line.max(1),
+                col = r.location.start_column.max(1),
+                severity = &r.severity,
+                path = &r.location.path,
+                message = &r.message,
+                how_to_fix = &r.how_to_fix,
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn is_error(result: &LintResult) -> bool {
+    matches!(result.severity, LintSeverityEnum::Error)
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
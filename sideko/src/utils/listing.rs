@@ -0,0 +1,326 @@
+use std::cmp::Ordering;
+
+use serde_json::Value;
+use tabled::Tabled;
+
+use crate::{
+    cmds::{DisplayOutput, ListOptions},
+    result::{CliError, CliResult, ErrorCode},
+};
+
+use super::{output, template::Template};
+
+/// prints a listing after applying the list options, a `--format` template takes
+/// priority over the display format
+///
+/// each row wraps a model with its table columns, fields of the options may refer to
+/// either of them
+pub fn print_list<T: serde::Serialize, R: Tabled>(
+    display: &DisplayOutput,
+    format: Option<&Template>,
+    title: &str,
+    options: &ListOptions,
+    rows: impl IntoIterator<Item = R>,
+    model: impl Fn(&R) -> &T,
+) -> CliResult<()> {
+    let entries = rows
+        .into_iter()
+        .map(|row| {
+            let raw = serde_json::to_value(model(&row))
+                .map_err(|e| CliError::general_debug("failed serializing result", e))?;
+            let cells = row.fields().into_iter().map(|f| f.into_owned()).collect();
+            Ok(Entry { raw, cells })
+        })
+        .collect::<CliResult<Vec<_>>>()?;
+
+    let mut listing = Listing {
+        headers: output::headers::<R>(),
+        entries,
+    };
+    listing.filter(&options.filter)?;
+    listing.sort(&options.sort)?;
+    match format {
+        Some(template) => listing.print_template(template, &options.columns),
+        None => listing.print(display, title, &options.columns),
+    }
+}
+
+struct Listing {
+    headers: Vec<String>,
+    entries: Vec<Entry>,
+}
+
+struct Entry {
+    /// serialized model
+    raw: Value,
+    /// table columns
+    cells: Vec<String>,
+}
+
+enum Field {
+    Column(usize),
+    /// dot separated path into the serialized model
+    Path(Vec<String>),
+}
+
+#[derive(Clone, Copy)]
+enum FilterOp {
+    Eq,
+    Ne,
+    Contains,
+    NotContains,
+    Gt,
+    Ge,
+    Lt,
+    Le,
+}
+
+/// two character operators first so they are not mistaken for their prefix
+const FILTER_OPS: &[(&str, FilterOp)] = &[
+    ("!=", FilterOp::Ne),
+    ("!~", FilterOp::NotContains),
+    (">=", FilterOp::Ge),
+    ("<=", FilterOp::Le),
+    ("=", FilterOp::Eq),
+    ("~", FilterOp::Contains),
+    (">", FilterOp::Gt),
+    ("<", FilterOp::Lt),
+];
+
+impl Listing {
+    /// resolves a table column by its header (e.g. `mock server` or `mock_server`) or
+    /// falls back to a path into the model
+    fn field(&self, name: &str) -> CliResult<Field> {
+        let key = normalize(name);
+        if let Some(idx) = self.headers.iter().position(|h| normalize(h) == key) {
+            return Ok(Field::Column(idx));
+        }
+
+        let path: Vec<String> = name.trim().split('.').map(String::from).collect();
+        // unknown fields can only be detected when there are rows to look them up in
+        if self.entries.is_empty() || self.entries.iter().any(|e| lookup(&e.raw, &path).is_some()) {
+            return Ok(Field::Path(path));
+        }
+
+        Err(CliError::general(format!(
+            "unknown field `{name}`, available fields: {}",
+            self.available_fields().join(", ")
+        ))
+        .with_code(ErrorCode::InvalidInput))
+    }
+
+    fn available_fields(&self) -> Vec<String> {
+        let mut fields: Vec<String> = self.headers.iter().map(|h| normalize(h)).collect();
+        if let Some(Value::Object(map)) = self.entries.first().map(|e| &e.raw) {
+            for key in map.keys() {
+                if !fields.contains(key) {
+                    fields.push(key.clone());
+                }
+            }
+        }
+
+        fields
+    }
+
+    fn filter(&mut self, filters: &[String]) -> CliResult<()> {
+        for filter in filters {
+            let (name, op, expected) = parse_filter(filter)?;
+            let field = self.field(name)?;
+            self.entries
+                .retain(|entry| op.matches(&entry.text(&field), expected));
+        }
+
+        Ok(())
+    }
+
+    fn sort(&mut self, sorts: &[String]) -> CliResult<()> {
+        let keys = sorts
+            .iter()
+            .map(|sort| {
+                let (name, desc) = match sort.rsplit_once(':') {
+                    Some((name, "desc")) => (name, true),
+                    Some((name, "asc")) => (name, false),
+                    _ => (sort.as_str(), false),
+                };
+                Ok((self.field(name)?, desc))
+            })
+            .collect::<CliResult<Vec<_>>>()?;
+        if keys.is_empty() {
+            return Ok(());
+        }
+
+        self.entries.sort_by(|a, b| {
+            keys.iter()
+                .map(|(field, desc)| {
+                    let ord = compare(&a.text(field), &b.text(field));
+                    if *desc {
+                        ord.reverse()
+                    } else {
+                        ord
+                    }
+                })
+                .find(|ord| ord.is_ne())
+                .unwrap_or(Ordering::Equal)
+        });
+
+        Ok(())
+    }
+
+    fn print(self, display: &DisplayOutput, title: &str, columns: &[String]) -> CliResult<()> {
+        if columns.is_empty() {
+            if display.is_structured() {
+                let raw: Vec<Value> = self.entries.into_iter().map(|e| e.raw).collect();
+                return output::print_data(display, &raw);
+            }
+            let records = self.entries.into_iter().map(|e| e.cells).collect();
+            return output::print_records(display, title, &self.headers, records);
+        }
+
+        if display.is_structured() {
+            return output::print_data(display, &self.objects(columns)?);
+        }
+
+        let fields = columns
+            .iter()
+            .map(|c| self.field(c))
+            .collect::<CliResult<Vec<_>>>()?;
+
+        let headers: Vec<String> = columns
+            .iter()
+            .zip(&fields)
+            .map(|(column, field)| match field {
+                Field::Column(idx) => self.headers[*idx].clone(),
+                Field::Path(_) => column.trim().to_string(),
+            })
+            .collect();
+        let records = self
+            .entries
+            .iter()
+            .map(|entry| fields.iter().map(|f| entry.text(f)).collect())
+            .collect();
+
+        output::print_records(display, title, &headers, records)
+    }
+
+    /// renders the template against each model, or the selected columns if any
+    fn print_template(self, template: &Template, columns: &[String]) -> CliResult<()> {
+        if columns.is_empty() {
+            let raw: Vec<Value> = self.entries.into_iter().map(|e| e.raw).collect();
+            return template.print(&raw);
+        }
+
+        template.print(&self.objects(columns)?)
+    }
+
+    /// rows as objects of the selected columns
+    fn objects(&self, columns: &[String]) -> CliResult<Vec<serde_json::Map<String, Value>>> {
+        let fields = columns
+            .iter()
+            .map(|c| self.field(c))
+            .collect::<CliResult<Vec<_>>>()?;
+
+        Ok(self
+            .entries
+            .iter()
+            .map(|entry| {
+                columns
+                    .iter()
+                    .zip(&fields)
+                    .map(|(column, field)| (column.trim().to_string(), entry.value(field)))
+                    .collect()
+            })
+            .collect())
+    }
+}
+
+impl Entry {
+    fn value(&self, field: &Field) -> Value {
+        match field {
+            Field::Column(idx) => Value::String(self.cells[*idx].clone()),
+            Field::Path(path) => lookup(&self.raw, path).cloned().unwrap_or(Value::Null),
+        }
+    }
+
+    fn text(&self, field: &Field) -> String {
+        match self.value(field) {
+            Value::Null => String::new(),
+            Value::String(s) => s,
+            val => val.to_string(),
+        }
+    }
+}
+
+impl FilterOp {
+    fn matches(&self, val: &str, expected: &str) -> bool {
+        let contains = || val.to_lowercase().contains(&expected.to_lowercase());
+        let ord = || compare(val, expected);
+        match self {
+            FilterOp::Eq => ord().is_eq(),
+            FilterOp::Ne => ord().is_ne(),
+            FilterOp::Contains => contains(),
+            FilterOp::NotContains => !contains(),
+            FilterOp::Gt => ord().is_gt(),
+            FilterOp::Ge => ord().is_ge(),
+            FilterOp::Lt => ord().is_lt(),
+            FilterOp::Le => ord().is_le(),
+        }
+    }
+}
+
+/// splits `<field><op><value>` e.g. `versions>=2`
+fn parse_filter(filter: &str) -> CliResult<(&str, FilterOp, &str)> {
+    let invalid = || {
+        CliError::general(format!(
+            "invalid filter `{filter}`, expected `<field><op><value>` with one of the ops: {}",
+            FILTER_OPS
+                .iter()
+                .map(|(op, _)| *op)
+                .collect::<Vec<_>>()
+                .join(" ")
+        ))
+        .with_code(ErrorCode::InvalidInput)
+    };
+
+    let start = filter.find(['=', '!', '~', '<', '>']).ok_or_else(invalid)?;
+    let (name, rest) = filter.split_at(start);
+    let (op_str, op) = FILTER_OPS
+        .iter()
+        .find(|(op, _)| rest.starts_with(op))
+        .ok_or_else(invalid)?;
+    if name.trim().is_empty() {
+        return Err(invalid());
+    }
+
+    Ok((name.trim(), *op, &rest[op_str.len()..]))
+}
+
+/// compares numbers and semantic versions by value, anything else case-insensitively
+fn compare(a: &str, b: &str) -> Ordering {
+    if let (Ok(a), Ok(b)) = (a.parse::<f64>(), b.parse::<f64>()) {
+        return a.partial_cmp(&b).unwrap_or(Ordering::Equal);
+    }
+    if let (Ok(a), Ok(b)) = (semver::Version::parse(a), semver::Version::parse(b)) {
+        return a.cmp(&b);
+    }
+
+    a.to_lowercase().cmp(&b.to_lowercase())
+}
+
+fn lookup<'a>(val: &'a Value, path: &[String]) -> Option<&'a Value> {
+    path.iter().try_fold(val, |val, key| match val {
+        Value::Object(map) => map.get(key),
+        Value::Array(items) => key.parse::<usize>().ok().and_then(|idx| items.get(idx)),
+        _ => None,
+    })
+}
+
+/// field names are matched ignoring case, emojis and whether words are separated by
+/// spaces, dashes or underscores
+fn normalize(name: &str) -> String {
+    name.chars()
+        .filter(|c| c.is_ascii())
+        .collect::<String>()
+        .trim()
+        .to_lowercase()
+        .replace([' ', '-'], "_")
+}
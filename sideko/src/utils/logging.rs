@@ -1,7 +1,69 @@
-use log::{info, Level};
-use std::io::Write;
-use tabled::settings::{peaker::Priority, Width};
-use terminal_size::{terminal_size, Height as TerminalHeight, Width as TerminalWidth};
+use camino::{Utf8Path, Utf8PathBuf};
+use log::{debug, info, Level, LevelFilter, Log, Metadata, Record};
+use std::{
+    fs,
+    io::Write,
+    sync::{Mutex, OnceLock},
+    time::Instant,
+};
+
+use crate::{
+    result::{CliError, CliResult, ErrorCode},
+    styles::{stderr_color, strip_ansi},
+};
+
+use super::config;
+
+/// the log file is rotated once it exceeds this size
+const LOG_FILE_MAX_BYTES: u64 = 10 * 1024 * 1024;
+/// rotated log files kept next to the log file, `<file>.1` being the most recent
+const LOG_FILE_BACKUPS: usize = 3;
+const REDACTED: &str = "[REDACTED]";
+
+static TERMINAL_LEVEL: OnceLock<LevelFilter> = OnceLock::new();
+static SESSION: OnceLock<Session> = OnceLock::new();
+static LOG_FILE: OnceLock<Mutex<LogFile>> = OnceLock::new();
+
+/// logs to the terminal and, once enabled, to the log file
+struct CliLogger {
+    terminal: env_logger::Logger,
+}
+
+/// the invoked command, recorded with each line of the log file
+struct Session {
+    command: String,
+    secrets: Vec<String>,
+    started: Instant,
+}
+
+/// json lines log file recording debug logs regardless of the terminal log level
+struct LogFile {
+    path: Utf8PathBuf,
+    file: fs::File,
+    size: u64,
+    /// api key of the active profile, redacted wherever it appears
+    api_key: Option<String>,
+}
+
+#[derive(serde::Serialize)]
+struct LogEntry<'a> {
+    timestamp: String,
+    level: String,
+    target: &'a str,
+    message: String,
+    command: &'a str,
+    #[serde(flatten, skip_serializing_if = "Option::is_none")]
+    outcome: Option<Outcome>,
+}
+
+/// recorded with the last line of a command
+#[derive(serde::Serialize)]
+struct Outcome {
+    duration_ms: u128,
+    exit_code: i32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error_code: Option<ErrorCode>,
+}
 
 pub fn init_logger(quiet: bool, verbose: u8) {
     let level = if quiet {
@@ -15,6 +77,11 @@ pub fn init_logger(quiet: bool, verbose: u8) {
     };
 
     let mut builder = env_logger::builder();
+    builder.write_style(if stderr_color() {
+        env_logger::WriteStyle::Always
+    } else {
+        env_logger::WriteStyle::Never
+    });
 
     if level == log::Level::Trace {
         builder.filter_level(level.to_level_filter());
@@ -39,22 +106,217 @@ pub fn init_logger(quiet: bool, verbose: u8) {
             });
     }
 
-    let _ = builder.try_init();
+    let terminal = builder.build();
+    let filter = terminal.filter();
+    if log::set_boxed_logger(Box::new(CliLogger { terminal })).is_ok() {
+        log::set_max_level(filter);
+        let _ = TERMINAL_LEVEL.set(level.to_level_filter());
+    }
 }
 
-pub fn log_json_raw<T: ?Sized + serde::Serialize>(val: &T) {
-    info!(
-        "{}",
-        serde_json::to_string_pretty(val).unwrap_or_else(|_| serde_json::json!(val).to_string())
-    )
+/// whether logs of the level are shown in the terminal, the log file may record more
+pub fn terminal_enabled(level: Level) -> bool {
+    TERMINAL_LEVEL.get().is_some_and(|filter| level <= *filter)
 }
 
-pub fn log_table(mut table: tabled::Table) {
-    // consistent table format that fits in existing terminal size
-    table.with(tabled::settings::Style::modern());
+/// writes a diagnostic line to stderr regardless of the terminal log level, e.g. the
+/// progress of long transfers when no spinner is shown
+pub fn diagnostic(msg: &str) {
+    let _ = writeln!(std::io::stderr().lock(), "{msg}");
+}
+
+/// remembers the invoked command for the log file, the secrets passed as arguments
+/// (e.g. `--api-key`) are redacted
+pub fn start_session(args: &[String], secrets: Vec<String>) {
+    let secrets: Vec<String> = secrets.into_iter().filter(|s| !s.is_empty()).collect();
+    let command = args
+        .iter()
+        .skip(1)
+        .map(|arg| {
+            secrets
+                .iter()
+                .fold(arg.clone(), |arg, secret| arg.replace(secret, REDACTED))
+        })
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    let _ = SESSION.set(Session {
+        command,
+        secrets,
+        started: Instant::now(),
+    });
+}
+
+/// starts recording debug logs of the command to the log file as json lines, must be
+/// called after the config is loaded so the api key can be redacted
+pub fn init_log_file(path: &Utf8Path) -> CliResult<()> {
+    if LOG_FILE.get().is_some() {
+        return Ok(());
+    }
+
+    if let Some(parent) = path.parent().filter(|p| !p.as_str().is_empty()) {
+        fs::create_dir_all(parent).map_err(|e| {
+            CliError::io_custom(format!("failed creating log file directory: {parent}"), e)
+        })?;
+    }
+    let size = fs::metadata(path).map(|m| m.len()).unwrap_or_default();
+    if size > LOG_FILE_MAX_BYTES {
+        rotate(path)?;
+    }
+
+    let log_file = LogFile {
+        path: path.to_path_buf(),
+        file: open_log_file(path)?,
+        size: fs::metadata(path).map(|m| m.len()).unwrap_or_default(),
+        api_key: config::get_api_key().filter(|k| !k.is_empty()),
+    };
+    if LOG_FILE.set(Mutex::new(log_file)).is_ok() {
+        log::set_max_level(log::max_level().max(LevelFilter::Debug));
+        debug!("sideko v{} logging to {path}", env!("CARGO_PKG_VERSION"));
+    }
+
+    Ok(())
+}
 
-    if let Some((TerminalWidth(width), TerminalHeight(_height))) = terminal_size() {
-        table.with(Width::wrap(width as usize).priority(Priority::max(true)));
+/// records how long the command took and how it exited to the log file
+pub fn end_session(res: &CliResult<()>) {
+    let Some(log_file) = LOG_FILE.get() else {
+        return;
+    };
+    let (level, message, error_code) = match res {
+        Ok(_) => (Level::Info, "command succeeded", None),
+        Err(e) => (Level::Error, "command failed", Some(e.code())),
+    };
+
+    let mut log_file = log_file.lock().unwrap_or_else(|e| e.into_inner());
+    log_file.write(
+        level,
+        module_path!(),
+        message,
+        Some(Outcome {
+            duration_ms: SESSION
+                .get()
+                .map(|s| s.started.elapsed().as_millis())
+                .unwrap_or_default(),
+            exit_code: error_code.as_ref().map_or(0, ErrorCode::exit_code),
+            error_code,
+        }),
+    );
+}
+
+impl Log for CliLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        self.terminal.enabled(metadata) || (LOG_FILE.get().is_some() && is_recorded(metadata))
+    }
+
+    fn log(&self, record: &Record) {
+        if self.terminal.matches(record) {
+            self.terminal.log(record);
+        }
+        if let Some(log_file) = LOG_FILE.get().filter(|_| is_recorded(record.metadata())) {
+            let mut log_file = log_file.lock().unwrap_or_else(|e| e.into_inner());
+            log_file.write(
+                record.level(),
+                record.target(),
+                &record.args().to_string(),
+                None,
+            );
+        }
+    }
+
+    fn flush(&self) {
+        self.terminal.flush();
+        if let Some(log_file) = LOG_FILE.get() {
+            let _ = log_file
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .file
+                .flush();
+        }
+    }
+}
+
+/// the log file records debug logs of the cli, dependencies are only logged with `-vv`
+fn is_recorded(metadata: &Metadata) -> bool {
+    metadata.level() <= Level::Debug && metadata.target().starts_with("sideko")
+}
+
+impl LogFile {
+    /// write errors are ignored, the log file must never fail the command
+    fn write(&mut self, level: Level, target: &str, message: &str, outcome: Option<Outcome>) {
+        let session = SESSION.get();
+        let entry = LogEntry {
+            timestamp: chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+            level: level.as_str().to_lowercase(),
+            target,
+            message: self.redact(&strip_ansi(message)),
+            command: session.map(|s| s.command.as_str()).unwrap_or_default(),
+            outcome,
+        };
+        let Ok(mut line) = serde_json::to_string(&entry) else {
+            return;
+        };
+        line.push('\n');
+
+        if self.file.write_all(line.as_bytes()).is_err() {
+            return;
+        }
+        self.size += line.len() as u64;
+        if self.size > LOG_FILE_MAX_BYTES && rotate(&self.path).is_ok() {
+            if let Ok(file) = open_log_file(&self.path) {
+                self.file = file;
+                self.size = 0;
+            }
+        }
+    }
+
+    fn redact(&self, text: &str) -> String {
+        let secrets = SESSION
+            .get()
+            .map(|s| s.secrets.iter())
+            .into_iter()
+            .flatten()
+            .chain(self.api_key.iter())
+            .filter(|s| !s.is_empty());
+
+        let mut text = text.to_string();
+        for secret in secrets {
+            text = text.replace(secret.as_str(), REDACTED);
+        }
+
+        text
+    }
+}
+
+fn open_log_file(path: &Utf8Path) -> CliResult<fs::File> {
+    fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(|e| CliError::io_custom(format!("failed opening log file: {path}"), e))
+}
+
+/// shifts `<file>` to `<file>.1`, `<file>.1` to `<file>.2` and so on, dropping the oldest
+fn rotate(path: &Utf8Path) -> CliResult<()> {
+    let backup = |idx: usize| Utf8PathBuf::from(format!("{path}.{idx}"));
+    for idx in (1..LOG_FILE_BACKUPS).rev() {
+        if backup(idx).exists() {
+            fs::rename(backup(idx), backup(idx + 1))
+                .map_err(|e| CliError::io_custom(format!("failed rotating log file: {path}"), e))?;
+        }
+    }
+
+    fs::rename(path, backup(1))
+        .map_err(|e| CliError::io_custom(format!("failed rotating log file: {path}"), e))
+}
+
+/// logs a table as a diagnostic (e.g. previews), results must use `output::print_table`
+pub fn log_table(mut table: tabled::Table) {
+    super::tabled::fit_table(&mut table);
+    let table = table.to_string();
+    if stderr_color() {
+        info!("\n{table}\n");
+    } else {
+        info!("\n{}\n", strip_ansi(&table));
     }
-    info!("\n{table}\n");
 }
@@ -0,0 +1,283 @@
+use std::sync::OnceLock;
+
+use camino::{Utf8Path, Utf8PathBuf};
+use log::debug;
+
+use crate::{
+    cmds::SdkLang,
+    result::{CliError, CliResult, ErrorCode},
+};
+
+use super::validators;
+
+/// file name of the project manifest, discovered by walking up from the working directory
+pub(crate) const MANIFEST_FILENAME: &str = "sideko.toml";
+
+static MANIFEST: OnceLock<Option<Manifest>> = OnceLock::new();
+
+/// repository-local project manifest providing defaults for command flags,
+/// paths in the manifest are relative to the directory containing it
+///
+/// ```toml
+/// [api]
+/// name = "my-api"
+/// spec = "openapi.yaml"
+/// version = "latest"
+///
+/// [doc]
+/// name = "my-docs"
+///
+/// [[sdk]]
+/// lang = "python"
+/// config = "sdk-config.yaml"
+/// repo = "sdks/python"
+/// ```
+#[derive(Debug, Default, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct Manifest {
+    #[serde(skip)]
+    pub path: Utf8PathBuf,
+    #[serde(default)]
+    pub api: ManifestApi,
+    #[serde(default)]
+    pub doc: ManifestDoc,
+    #[serde(default)]
+    pub sdk: Vec<ManifestSdk>,
+}
+
+#[derive(Debug, Default, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct ManifestApi {
+    /// api name or id
+    pub name: Option<String>,
+    /// path to the openapi spec of the api
+    pub spec: Option<Utf8PathBuf>,
+    /// api version sdks are generated from
+    pub version: Option<String>,
+}
+
+#[derive(Debug, Default, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct ManifestDoc {
+    /// doc project name or id
+    pub name: Option<String>,
+}
+
+#[derive(Debug, Clone, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct ManifestSdk {
+    /// sdk language (e.g. `python`)
+    pub lang: SdkLang,
+    /// path to the sdk config
+    pub config: Option<Utf8PathBuf>,
+    /// path to the root of the sdk repo
+    pub repo: Option<Utf8PathBuf>,
+}
+
+impl Manifest {
+    /// walks up from the directory to find the closest manifest
+    fn discover(start: &Utf8Path) -> CliResult<Option<Self>> {
+        for dir in start.ancestors() {
+            let path = dir.join(MANIFEST_FILENAME);
+            if path.is_file() {
+                return Self::from_path(&path).map(Some);
+            }
+        }
+
+        Ok(None)
+    }
+
+    fn from_path(path: &Utf8Path) -> CliResult<Self> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| CliError::io_custom(format!("failed reading manifest: {path}"), e))?;
+        let mut manifest: Manifest = toml::from_str(&content).map_err(|e| {
+            CliError::general_debug(format!("invalid manifest {path}: {}", e.message()), e)
+                .with_code(ErrorCode::Config)
+        })?;
+
+        // resolve paths relative to the manifest
+        let root = path.parent().unwrap_or(Utf8Path::new(".")).to_path_buf();
+        manifest.api.spec = manifest.api.spec.map(|p| root.join(p));
+        for sdk in &mut manifest.sdk {
+            sdk.config = sdk.config.take().map(|p| root.join(p));
+            sdk.repo = sdk.repo.take().map(|p| root.join(p));
+        }
+        manifest.path = path.to_path_buf();
+
+        Ok(manifest)
+    }
+
+    /// selects the sdk entry matching the given flags, when multiple entries match
+    /// the one containing the working directory is preferred
+    pub fn select_sdk(
+        &self,
+        lang: Option<&SdkLang>,
+        config: Option<&Utf8PathBuf>,
+        repo: Option<&Utf8PathBuf>,
+    ) -> CliResult<Option<&ManifestSdk>> {
+        let candidates: Vec<&ManifestSdk> = self
+            .sdk
+            .iter()
+            .filter(|s| lang.is_none_or(|l| &s.lang == l))
+            .filter(|s| config.is_none_or(|c| s.config.as_ref().is_some_and(|sc| same_path(sc, c))))
+            .filter(|s| repo.is_none_or(|r| s.repo.as_ref().is_some_and(|sr| same_path(sr, r))))
+            .collect();
+
+        if candidates.len() <= 1 {
+            return Ok(candidates.first().copied());
+        }
+
+        let cwd = current_dir()?;
+        let containing: Vec<&ManifestSdk> = candidates
+            .iter()
+            .copied()
+            .filter(|s| s.repo.as_ref().is_some_and(|r| is_within(&cwd, r)))
+            .collect();
+        if containing.len() == 1 {
+            return Ok(Some(containing[0]));
+        }
+
+        Err(CliError::general(format!(
+            "multiple sdks in {} match ({}), select one with --lang",
+            &self.path,
+            candidates
+                .iter()
+                .map(|s| s.lang.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        ))
+        .with_code(ErrorCode::Config))
+    }
+}
+
+/// discovers the project manifest from the working directory, must be called before
+/// any of the default resolvers
+pub(crate) fn load() -> CliResult<()> {
+    let manifest = Manifest::discover(&current_dir()?)?;
+    if let Some(m) = &manifest {
+        debug!("loaded project manifest: {}", &m.path);
+    }
+    let _ = MANIFEST.set(manifest);
+
+    Ok(())
+}
+
+/// project manifest discovered by `load`
+pub(crate) fn get() -> Option<&'static Manifest> {
+    MANIFEST.get().and_then(|m| m.as_ref())
+}
+
+/// resolves the api name from the flag or `api.name` of the manifest
+pub(crate) fn api_name(flag: Option<&String>) -> CliResult<String> {
+    flag.cloned()
+        .or_else(|| get().and_then(|m| m.api.name.clone()))
+        .ok_or_else(|| missing("--name", "api.name"))
+}
+
+/// resolves the openapi spec from the flag or `api.spec` of the manifest
+pub(crate) fn api_spec(flag: Option<&Utf8PathBuf>) -> CliResult<Utf8PathBuf> {
+    api_spec_opt(flag)?.ok_or_else(|| missing("--spec", "api.spec"))
+}
+
+/// resolves the openapi spec from the flag or `api.spec` of the manifest if either is set
+pub(crate) fn api_spec_opt(flag: Option<&Utf8PathBuf>) -> CliResult<Option<Utf8PathBuf>> {
+    if let Some(spec) = flag {
+        return Ok(Some(spec.clone()));
+    }
+    get()
+        .and_then(|m| m.api.spec.as_ref())
+        .map(|spec| validate("api.spec", spec, validators::validate_file_json_yaml))
+        .transpose()
+}
+
+/// resolves the api version to generate from, defaulting to `latest`
+pub(crate) fn api_version(flag: Option<&String>) -> String {
+    flag.cloned()
+        .or_else(|| get().and_then(|m| m.api.version.clone()))
+        .unwrap_or_else(|| "latest".into())
+}
+
+/// resolves the doc project from the flag or `doc.name` of the manifest
+pub(crate) fn doc_name(flag: Option<&String>) -> CliResult<String> {
+    flag.cloned()
+        .or_else(|| get().and_then(|m| m.doc.name.clone()))
+        .ok_or_else(|| missing("--name", "doc.name"))
+}
+
+/// selects the sdk entry of the manifest matching the given flags
+pub(crate) fn sdk(
+    lang: Option<&SdkLang>,
+    config: Option<&Utf8PathBuf>,
+    repo: Option<&Utf8PathBuf>,
+) -> CliResult<Option<&'static ManifestSdk>> {
+    match get() {
+        Some(m) => m.select_sdk(lang, config, repo),
+        None => Ok(None),
+    }
+}
+
+/// resolves the sdk config from the flag or the selected sdk entry of the manifest
+pub(crate) fn sdk_config(
+    flag: Option<&Utf8PathBuf>,
+    sdk: Option<&ManifestSdk>,
+) -> CliResult<Utf8PathBuf> {
+    if let Some(config) = flag {
+        return Ok(config.clone());
+    }
+    match sdk.and_then(|s| s.config.as_ref()) {
+        Some(config) => validate("sdk.config", config, validators::validate_file_yaml),
+        None => Err(missing("--config", "sdk.config")),
+    }
+}
+
+/// resolves the sdk repo from the flag or the selected sdk entry of the manifest
+pub(crate) fn sdk_repo(
+    flag: Option<&Utf8PathBuf>,
+    sdk: Option<&ManifestSdk>,
+) -> CliResult<Option<Utf8PathBuf>> {
+    if let Some(repo) = flag {
+        return Ok(Some(repo.clone()));
+    }
+    sdk.and_then(|s| s.repo.as_ref())
+        .map(|repo| validate("sdk.repo", repo, validators::validate_dir))
+        .transpose()
+}
+
+fn validate(
+    field: &str,
+    path: &Utf8PathBuf,
+    validator: fn(&str) -> Result<Utf8PathBuf, String>,
+) -> CliResult<Utf8PathBuf> {
+    validator(path.as_str()).map_err(|e| {
+        let manifest = get().map(|m| m.path.to_string()).unwrap_or_default();
+        CliError::general(format!("invalid `{field}` in {manifest}: {e}"))
+            .with_code(ErrorCode::Config)
+    })
+}
+
+fn missing(flag: &str, field: &str) -> CliError {
+    CliError::general(format!(
+        "{flag} is required (or set `{field}` in a {MANIFEST_FILENAME} in this or a parent directory)"
+    ))
+    .with_code(ErrorCode::InvalidInput)
+}
+
+fn current_dir() -> CliResult<Utf8PathBuf> {
+    let cwd = std::env::current_dir()
+        .map_err(|e| CliError::io_custom("failed reading current directory", e))?;
+    Utf8PathBuf::from_path_buf(cwd)
+        .map_err(|p| CliError::general(format!("current directory is not valid utf-8: {p:?}")))
+}
+
+fn canonical(path: &Utf8Path) -> Utf8PathBuf {
+    path.canonicalize_utf8()
+        .unwrap_or_else(|_| path.to_path_buf())
+}
+
+fn same_path(a: &Utf8Path, b: &Utf8Path) -> bool {
+    canonical(a) == canonical(b)
+}
+
+fn is_within(path: &Utf8Path, dir: &Utf8Path) -> bool {
+    canonical(path).starts_with(canonical(dir))
+}
@@ -1,58 +1,41 @@
-use log::{debug, error};
-use sideko_rest_api::{
-    models::{CliUpdate, CliUpdateSeverityEnum},
-    resources::cli::CheckUpdatesRequest,
-    SidekoClient,
-};
-
-use crate::result::{CliError, CliResult};
+use sideko_rest_api::SidekoClient;
 
+pub(crate) mod archive;
 pub(crate) mod config;
+pub(crate) mod credentials;
+pub(crate) mod github;
+pub(crate) mod http;
+pub(crate) mod junit;
+pub(crate) mod listing;
 pub(crate) mod logging;
+pub(crate) mod manifest;
+pub(crate) mod output;
+pub(crate) mod progress;
 pub(crate) mod response;
+pub(crate) mod sarif;
+pub(crate) mod spec_diff;
 pub(crate) mod spinner;
 pub(crate) mod tabled;
+pub(crate) mod template;
+pub(crate) mod updates;
 pub(crate) mod url_builder;
 pub(crate) mod validators;
 
+/// initializes an unauthenticated SidekoClient using the network settings from config environment
+pub(crate) fn get_base_sideko_client() -> SidekoClient {
+    let client = SidekoClient::default().with_environment(config::get_base_url());
+    match http::client() {
+        Some(http_client) => client.with_client(http_client),
+        None => client,
+    }
+}
+
 /// initializes SidekoClient using base url & api key from config environment
 pub(crate) fn get_sideko_client() -> SidekoClient {
-    let mut client = SidekoClient::default().with_environment(config::get_base_url());
+    let mut client = get_base_sideko_client();
     if let Some(key) = config::get_api_key() {
         client = client.with_api_key_auth(&key);
     }
 
     client
 }
-
-/// Uses the sideko api to check for cli notices/update requirements, returning an Err
-/// if a required update is detected
-///
-/// Returns all non-required updates to be logged at the end of the command
-pub async fn check_for_updates() -> CliResult<Vec<CliUpdate>> {
-    let cli_version = env!("CARGO_PKG_VERSION").to_string();
-    debug!("checking for updates (cli version: {cli_version})...");
-
-    let mut client = SidekoClient::default().with_environment(config::get_base_url());
-    let updates = client
-        .cli()
-        .check_updates(CheckUpdatesRequest { cli_version })
-        .await?;
-
-    let mut early_exit = false;
-    for update in &updates {
-        if matches!(&update.severity, CliUpdateSeverityEnum::Required) {
-            error!("{}", update.message);
-            early_exit = true;
-        }
-    }
-
-    if early_exit {
-        return Err(CliError::general("must update cli to continue"));
-    }
-
-    Ok(updates
-        .into_iter()
-        .filter(|u| !matches!(&u.severity, CliUpdateSeverityEnum::Required))
-        .collect())
-}
@@ -0,0 +1,165 @@
+use std::{fmt::Display, io::Write};
+
+use tabled::{
+    builder::Builder,
+    settings::{object::Rows, Color, Style},
+    Table, Tabled,
+};
+
+use crate::{
+    cmds::DisplayOutput,
+    result::{CliError, CliResult},
+    styles::{stdout_color, strip_ansi},
+};
+
+/// Results of a command are written to stdout so they can be piped or parsed (e.g.
+/// `sideko api list --display raw | jq`) and are not affected by `--quiet`, while
+/// logs, spinners and notices are written to stderr
+///
+/// write errors (e.g. a closed pipe) are ignored
+pub fn print_line<S: Display>(val: S) {
+    let _ = writeln!(std::io::stdout().lock(), "{val}");
+}
+
+pub fn print_json_raw<T: ?Sized + serde::Serialize>(val: &T) {
+    print_line(
+        serde_json::to_string_pretty(val).unwrap_or_else(|_| serde_json::json!(val).to_string()),
+    )
+}
+
+/// colors of the table are removed unless enabled for stdout
+pub fn print_table(mut table: tabled::Table) {
+    super::tabled::fit_table(&mut table);
+    let table = table.to_string();
+    if stdout_color() {
+        print_line(format!("\n{table}\n"));
+    } else {
+        print_line(format!("\n{}\n", strip_ansi(&table)));
+    }
+}
+
+/// prints the result of a command in one of the structured formats (raw, yaml, jsonl),
+/// jsonl writes each item of a list on its own line
+///
+/// tabular formats are expected to be printed with `print_rows`
+pub fn print_data<T: ?Sized + serde::Serialize>(display: &DisplayOutput, val: &T) -> CliResult<()> {
+    match display {
+        DisplayOutput::Yaml => {
+            let yaml = serde_norway::to_string(val)
+                .map_err(|e| CliError::general_debug("failed serializing result as yaml", e))?;
+            print_line(yaml.trim_end());
+        }
+        DisplayOutput::Jsonl => {
+            let json = serde_json::to_value(val)
+                .map_err(|e| CliError::general_debug("failed serializing result as json", e))?;
+            match json {
+                serde_json::Value::Array(items) => items.iter().for_each(print_line),
+                json => print_line(json),
+            }
+        }
+        _ => print_json_raw(val),
+    }
+
+    Ok(())
+}
+
+/// prints the result of a command in one of the tabular formats (pretty, csv, markdown),
+/// the `Tabled` implementation of the rows defines the columns
+///
+/// structured formats print each row as an object keyed by the column headers
+pub fn print_rows<R: Tabled>(
+    display: &DisplayOutput,
+    title: &str,
+    rows: impl IntoIterator<Item = R>,
+) -> CliResult<()> {
+    print_records(display, title, &headers::<R>(), records(rows))
+}
+
+/// column headers of the rows
+pub fn headers<R: Tabled>() -> Vec<String> {
+    R::headers().into_iter().map(|h| h.into_owned()).collect()
+}
+
+/// cells of the rows
+pub fn records<R: Tabled>(rows: impl IntoIterator<Item = R>) -> Vec<Vec<String>> {
+    rows.into_iter()
+        .map(|row| row.fields().into_iter().map(|f| f.into_owned()).collect())
+        .collect()
+}
+
+/// prints rows of cells in the given format, see `print_rows`
+pub fn print_records(
+    display: &DisplayOutput,
+    title: &str,
+    headers: &[String],
+    records: Vec<Vec<String>>,
+) -> CliResult<()> {
+    match display {
+        DisplayOutput::Pretty => {
+            let mut table = table(headers, &records, |c| c.to_string());
+            super::tabled::header_panel(&mut table, title);
+            table.modify(Rows::single(1), Color::BOLD);
+            print_table(table);
+        }
+        DisplayOutput::Csv => {
+            let mut stdout = std::io::stdout().lock();
+            let _ = write_csv(&mut stdout, headers, &records);
+        }
+        DisplayOutput::Markdown => print_line(markdown_table(title, headers, &records)),
+        structured => {
+            let objects: Vec<serde_json::Map<String, serde_json::Value>> = records
+                .into_iter()
+                .map(|record| {
+                    headers
+                        .iter()
+                        .zip(record)
+                        .map(|(h, f)| (h.clone(), f.into()))
+                        .collect()
+                })
+                .collect();
+            print_data(structured, &objects)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// writes the rows as csv with a header record
+pub fn write_csv<W: Write>(
+    writer: W,
+    headers: &[String],
+    records: &[Vec<String>],
+) -> CliResult<()> {
+    let map_err = |e: csv::Error| CliError::general_debug("failed writing csv", e);
+
+    let mut wtr = csv::Writer::from_writer(writer);
+    wtr.write_record(headers).map_err(map_err)?;
+    for record in records {
+        wtr.write_record(record).map_err(map_err)?;
+    }
+    wtr.flush()
+        .map_err(|e| CliError::io_custom("failed writing csv", e))
+}
+
+fn table(headers: &[String], records: &[Vec<String>], cell: impl Fn(&str) -> String) -> Table {
+    let mut builder = Builder::default();
+    builder.push_record(headers.iter().map(|h| cell(h)));
+    for record in records {
+        builder.push_record(record.iter().map(|c| cell(c)));
+    }
+
+    builder.build()
+}
+
+/// github flavored markdown table under a heading, cells are escaped so pipes and
+/// line breaks do not break the table
+fn markdown_table(title: &str, headers: &[String], records: &[Vec<String>]) -> String {
+    let mut table = table(headers, records, |cell| {
+        cell.replace('|', "\\|")
+            .replace("\r\n", "<br>")
+            .replace('\n', "<br>")
+    });
+    table.with(Style::markdown());
+
+    format!("### {title}\n\n{table}\n")
+}
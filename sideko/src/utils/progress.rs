@@ -0,0 +1,261 @@
+use std::{
+    borrow::Cow,
+    fmt::Display,
+    io::Write,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use super::{logging, spinner::Spinner};
+
+/// how often the spinner text is refreshed
+const RENDER_INTERVAL: Duration = Duration::from_millis(200);
+/// how often progress is logged when the spinner is not shown (quiet, not a terminal)
+const LOG_INTERVAL: Duration = Duration::from_secs(5);
+const BAR_WIDTH: usize = 20;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+    Tar,
+    Processing,
+}
+
+impl Display for Phase {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let phase = match self {
+            Phase::Tar => "tarring",
+            Phase::Processing => "waiting for sideko",
+        };
+        write!(f, "{phase}")
+    }
+}
+
+/// spinner showing the phase, size and rate of a long running step (e.g. tarring sdk
+/// archives) and how long the sideko api has been processing the request
+///
+/// without a spinner (quiet or not a terminal) the progress is written periodically as
+/// a diagnostic
+pub struct Progress {
+    shared: Arc<Shared>,
+    ticker: tokio::task::JoinHandle<()>,
+}
+
+struct Shared {
+    label: String,
+    sp: Mutex<Spinner>,
+    animated: bool,
+    state: Mutex<State>,
+}
+
+struct State {
+    phase: Phase,
+    done: u64,
+    total: Option<u64>,
+    started: Instant,
+    rendered: Option<Instant>,
+}
+
+impl Progress {
+    pub fn new<M: Into<String>>(label: M, phase: Phase) -> Self {
+        let label = label.into();
+        let sp = Spinner::new(label.clone());
+        let shared = Arc::new(Shared {
+            label,
+            animated: sp.is_animated(),
+            sp: Mutex::new(sp),
+            state: Mutex::new(State {
+                phase,
+                done: 0,
+                total: None,
+                started: Instant::now(),
+                rendered: None,
+            }),
+        });
+
+        // keeps the elapsed time and rate current while no bytes are written
+        let ticking = shared.clone();
+        let ticker = tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(RENDER_INTERVAL).await;
+                ticking.render(false);
+            }
+        });
+
+        Self { shared, ticker }
+    }
+
+    /// switches to a phase without progress in bytes, e.g. while waiting for a response
+    pub fn phase(&self, phase: Phase) {
+        self.shared.update(phase, 0, None);
+    }
+
+    /// wraps a writer reporting the bytes written to it as the progress of the phase
+    pub fn writer<W: Write>(
+        &self,
+        phase: Phase,
+        total: Option<u64>,
+        inner: W,
+    ) -> ProgressWriter<W> {
+        self.shared.update(phase, 0, total);
+        ProgressWriter {
+            inner,
+            shared: self.shared.clone(),
+            phase,
+            total,
+            written: 0,
+        }
+    }
+
+    pub fn stop_success<M: Into<Cow<'static, str>>>(&mut self, msg: M) {
+        self.finish();
+        self.shared.spinner().stop_success(msg);
+    }
+
+    pub fn stop_warn<M: Into<Cow<'static, str>>>(&mut self, msg: M) {
+        self.finish();
+        self.shared.spinner().stop_warn(msg);
+    }
+
+    pub fn stop_error<M: Into<Cow<'static, str>>>(&mut self, msg: M) {
+        self.finish();
+        self.shared.spinner().stop_error(msg);
+    }
+
+    fn finish(&self) {
+        self.ticker.abort();
+    }
+}
+
+impl Drop for Progress {
+    fn drop(&mut self) {
+        self.finish();
+    }
+}
+
+impl Shared {
+    fn spinner(&self) -> std::sync::MutexGuard<'_, Spinner> {
+        self.sp.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn update(&self, phase: Phase, done: u64, total: Option<u64>) {
+        let changed = {
+            let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+            let changed = state.phase != phase;
+            if changed {
+                state.phase = phase;
+                state.started = Instant::now();
+            }
+            state.done = done;
+            state.total = total;
+            changed
+        };
+
+        self.render(changed);
+    }
+
+    /// renders at most once per interval unless forced (e.g. when the phase changes)
+    fn render(&self, force: bool) {
+        let status = {
+            let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+            let interval = if self.animated {
+                RENDER_INTERVAL
+            } else {
+                LOG_INTERVAL
+            };
+            if !force && state.rendered.is_some_and(|r| r.elapsed() < interval) {
+                return;
+            }
+            state.rendered = Some(Instant::now());
+            state.status(self.animated)
+        };
+
+        if self.animated {
+            self.spinner()
+                .update_text(format!("{} · {status}", &self.label));
+        } else {
+            // written regardless of the log level, e.g. to show ci runs are not stuck
+            logging::diagnostic(&format!("{} · {status}", &self.label));
+        }
+    }
+}
+
+impl State {
+    /// e.g. `tarring ████████░░░░ 52% · 20.1 MiB / 38.6 MiB · 4.2 MiB/s`
+    fn status(&self, bar: bool) -> String {
+        let elapsed = self.started.elapsed();
+        if self.phase == Phase::Processing {
+            return format!("{} · {}s", self.phase, elapsed.as_secs());
+        }
+
+        // the rate is unreliable until some time has passed
+        let rate = match elapsed.as_secs_f64() {
+            secs if secs >= 0.5 => format!(" · {}/s", fmt_bytes((self.done as f64 / secs) as u64)),
+            _ => String::new(),
+        };
+        let Some(total) = self.total.filter(|t| *t > 0) else {
+            return format!("{} {}{rate}", self.phase, fmt_bytes(self.done));
+        };
+
+        let ratio = (self.done as f64 / total as f64).min(1.0);
+        let mut status = self.phase.to_string();
+        if bar {
+            let filled = (ratio * BAR_WIDTH as f64).round() as usize;
+            status.push_str(&format!(
+                " {}{}",
+                "█".repeat(filled),
+                "░".repeat(BAR_WIDTH - filled)
+            ));
+        }
+        format!(
+            "{status} {:.0}% · {} / {}{rate}",
+            ratio * 100.0,
+            fmt_bytes(self.done.min(total)),
+            fmt_bytes(total),
+        )
+    }
+}
+
+/// see `Progress::writer`
+pub struct ProgressWriter<W: Write> {
+    inner: W,
+    shared: Arc<Shared>,
+    phase: Phase,
+    total: Option<u64>,
+    written: u64,
+}
+
+impl<W: Write> ProgressWriter<W> {
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W: Write> Write for ProgressWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let n = self.inner.write(buf)?;
+        self.written += n as u64;
+        self.shared.update(self.phase, self.written, self.total);
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// human readable size, e.g. `20.1 MiB`
+pub fn fmt_bytes(bytes: u64) -> String {
+    const UNITS: &[&str] = &["B", "KiB", "MiB", "GiB", "TiB"];
+    let mut val = bytes as f64;
+    let mut unit = 0;
+    while val >= 1024.0 && unit < UNITS.len() - 1 {
+        val /= 1024.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{val:.1} {}", UNITS[unit])
+    }
+}
@@ -0,0 +1,199 @@
+use sideko_rest_api::models::{LintResult, LintSeverityEnum};
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+const SARIF_VERSION: &str = "2.1.0";
+
+/// sarif 2.1.0 log of lint results, understood by github code scanning and most ides
+#[derive(Debug, serde::Serialize)]
+pub struct SarifLog {
+    #[serde(rename = "$schema")]
+    schema: &'static str,
+    version: &'static str,
+    runs: Vec<Run>,
+}
+
+#[derive(Debug, serde::Serialize)]
+struct Run {
+    tool: Tool,
+    results: Vec<SarifResult>,
+}
+
+#[derive(Debug, serde::Serialize)]
+struct Tool {
+    driver: Driver,
+}
+
+#[derive(Debug, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Driver {
+    name: &'static str,
+    version: &'static str,
+    information_uri: &'static str,
+    rules: Vec<Rule>,
+}
+
+#[derive(Debug, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Rule {
+    id: String,
+    short_description: Message,
+    help: Message,
+    default_configuration: Configuration,
+    properties: Properties,
+}
+
+#[derive(Debug, serde::Serialize)]
+struct Configuration {
+    level: &'static str,
+}
+
+#[derive(Debug, serde::Serialize)]
+struct Properties {
+    category: String,
+    tags: Vec<String>,
+}
+
+#[derive(Debug, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifResult {
+    rule_id: String,
+    rule_index: usize,
+    level: &'static str,
+    message: Message,
+    locations: Vec<Location>,
+    properties: Properties,
+}
+
+#[derive(Debug, serde::Serialize)]
+struct Message {
+    text: String,
+}
+
+#[derive(Debug, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Location {
+    physical_location: PhysicalLocation,
+    logical_locations: Vec<LogicalLocation>,
+}
+
+#[derive(Debug, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PhysicalLocation {
+    artifact_location: ArtifactLocation,
+    region: Region,
+}
+
+#[derive(Debug, serde::Serialize)]
+struct ArtifactLocation {
+    uri: String,
+}
+
+/// sarif lines and columns start at 1
+#[derive(Debug, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Region {
+    start_line: i64,
+    start_column: i64,
+    end_line: i64,
+    end_column: i64,
+}
+
+#[derive(Debug, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct LogicalLocation {
+    fully_qualified_name: String,
+}
+
+impl SarifLog {
+    /// lint results of the spec at `uri` (relative to the repository root for code
+    /// scanning), each distinct rule is described once in the tool's rules
+    pub fn new(uri: &str, results: &[LintResult]) -> Self {
+        let uri = uri.strip_prefix("./").unwrap_or(uri).replace('\\', "/");
+        let mut rules: Vec<Rule> = vec![];
+        let results = results
+            .iter()
+            .map(|result| {
+                let rule_index = match rules.iter().position(|r| r.id == result.rule) {
+                    Some(idx) => idx,
+                    None => {
+                        rules.push(Rule {
+                            id: result.rule.clone(),
+                            short_description: Message {
+                                text: result.message.clone(),
+                            },
+                            help: Message {
+                                text: result.how_to_fix.clone(),
+                            },
+                            default_configuration: Configuration {
+                                level: level(&result.severity),
+                            },
+                            properties: Properties::new(&result.category),
+                        });
+                        rules.len() - 1
+                    }
+                };
+
+                let loc = &result.location;
+                let start_line = loc.start_line.max(1);
+                let start_column = loc.start_column.max(1);
+                SarifResult {
+                    rule_id: result.rule.clone(),
+                    rule_index,
+                    level: level(&result.severity),
+                    message: Message {
+                        text: result.message.clone(),
+                    },
+                    locations: vec![Location {
+                        physical_location: PhysicalLocation {
+                            artifact_location: ArtifactLocation { uri: uri.clone() },
+                            region: Region {
+                                start_line,
+                                start_column,
+                                end_line: loc.end_line.max(start_line),
+                                end_column: loc.end_column.max(1),
+                            },
+                        },
+                        logical_locations: vec![LogicalLocation {
+                            fully_qualified_name: loc.path.clone(),
+                        }],
+                    }],
+                    properties: Properties::new(&result.category),
+                }
+            })
+            .collect();
+
+        Self {
+            schema: SARIF_SCHEMA,
+            version: SARIF_VERSION,
+            runs: vec![Run {
+                tool: Tool {
+                    driver: Driver {
+                        name: "sideko",
+                        version: env!("CARGO_PKG_VERSION"),
+                        information_uri: "https://docs.sideko.dev",
+                        rules,
+                    },
+                },
+                results,
+            }],
+        }
+    }
+}
+
+impl Properties {
+    fn new(category: &str) -> Self {
+        Self {
+            category: category.to_string(),
+            tags: vec![category.to_string()],
+        }
+    }
+}
+
+fn level(severity: &LintSeverityEnum) -> &'static str {
+    match severity {
+        LintSeverityEnum::Error => "error",
+        LintSeverityEnum::Warn => "warning",
+        LintSeverityEnum::Info => "note",
+        LintSeverityEnum::Unknown => "none",
+    }
+}
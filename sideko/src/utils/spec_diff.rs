@@ -0,0 +1,1245 @@
+use std::collections::BTreeMap;
+
+use serde_json::{Map, Value};
+
+const METHODS: &[&str] = &[
+    "get", "put", "post", "delete", "options", "head", "patch", "trace",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    Changed,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Element {
+    Path,
+    Operation,
+    Parameter,
+    RequestBody,
+    Response,
+    Schema,
+}
+
+/// a single difference between two openapi specs
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct Change {
+    pub kind: ChangeKind,
+    pub element: Element,
+    /// operation (e.g. `GET /pets`), path or schema name the change belongs to
+    pub location: String,
+    pub detail: String,
+    /// whether existing clients of the api may break
+    pub breaking: bool,
+    /// whether only documentation (summaries and descriptions) changed
+    #[serde(skip)]
+    pub docs: bool,
+}
+
+impl ChangeKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ChangeKind::Added => "added",
+            ChangeKind::Removed => "removed",
+            ChangeKind::Changed => "changed",
+        }
+    }
+}
+
+impl Element {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Element::Path => "path",
+            Element::Operation => "operation",
+            Element::Parameter => "parameter",
+            Element::RequestBody => "request body",
+            Element::Response => "response",
+            Element::Schema => "schema",
+        }
+    }
+}
+
+/// compares the paths, operations, parameters, request bodies, responses and component
+/// schemas of two openapi specs (yaml or json parsed into json values)
+///
+/// references are compared by name, referenced schemas are compared as components, classified
+/// by whether they are reached from requests, responses or both
+pub fn diff(from: &Value, to: &Value) -> Vec<Change> {
+    let mut diff = Diff::default();
+    for (name, usage) in schema_usage(from).into_iter().chain(schema_usage(to)) {
+        let known = diff.usage.entry(name).or_default();
+        *known = known.union(usage);
+    }
+    diff.paths(from, to);
+    diff.schemas(from, to);
+
+    diff.changes
+}
+
+#[derive(Default)]
+struct Diff {
+    changes: Vec<Change>,
+    /// directions the component schemas are used in
+    usage: BTreeMap<String, Usage>,
+}
+
+impl Diff {
+    fn push<L: ToString, D: ToString>(
+        &mut self,
+        kind: ChangeKind,
+        element: Element,
+        location: L,
+        detail: D,
+        breaking: bool,
+    ) {
+        self.changes.push(Change {
+            kind,
+            element,
+            location: location.to_string(),
+            detail: detail.to_string(),
+            breaking,
+            docs: false,
+        })
+    }
+
+    fn push_docs<L: ToString, D: ToString>(&mut self, element: Element, location: L, detail: D) {
+        self.changes.push(Change {
+            kind: ChangeKind::Changed,
+            element,
+            location: location.to_string(),
+            detail: detail.to_string(),
+            breaking: false,
+            docs: true,
+        })
+    }
+
+    fn paths(&mut self, from: &Value, to: &Value) {
+        let (from_paths, to_paths) = (object(from, "paths"), object(to, "paths"));
+        for (path, from_item) in from_paths {
+            match to_paths.get(path) {
+                Some(to_item) => self.path_item(path, from_item, to_item),
+                None => self.push(
+                    ChangeKind::Removed,
+                    Element::Path,
+                    path,
+                    "path removed",
+                    true,
+                ),
+            }
+        }
+        for path in to_paths.keys().filter(|p| !from_paths.contains_key(*p)) {
+            self.push(ChangeKind::Added, Element::Path, path, "path added", false);
+        }
+    }
+
+    fn path_item(&mut self, path: &str, from_item: &Value, to_item: &Value) {
+        for method in METHODS {
+            let location = format!("{} {path}", method.to_uppercase());
+            match (from_item.get(method), to_item.get(method)) {
+                (Some(_), None) => self.push(
+                    ChangeKind::Removed,
+                    Element::Operation,
+                    location,
+                    "operation removed",
+                    true,
+                ),
+                (None, Some(_)) => self.push(
+                    ChangeKind::Added,
+                    Element::Operation,
+                    location,
+                    "operation added",
+                    false,
+                ),
+                (Some(from), Some(to)) => {
+                    self.operation(&location, from, to);
+                    self.parameters(
+                        &location,
+                        parameters(from_item, from),
+                        parameters(to_item, to),
+                    );
+                    self.request_body(&location, from.get("requestBody"), to.get("requestBody"));
+                    self.responses(
+                        &location,
+                        object(from, "responses"),
+                        object(to, "responses"),
+                    );
+                }
+                (None, None) => (),
+            }
+        }
+    }
+
+    fn operation(&mut self, location: &str, from: &Value, to: &Value) {
+        let (from_id, to_id) = (text(from, "operationId"), text(to, "operationId"));
+        if from_id != to_id {
+            // generated sdks name their methods after the operation id
+            self.push(
+                ChangeKind::Changed,
+                Element::Operation,
+                location,
+                format!("operationId changed from `{from_id}` to `{to_id}`"),
+                true,
+            );
+        }
+        for field in ["summary", "description"] {
+            if from.get(field) != to.get(field) {
+                self.push_docs(Element::Operation, location, format!("{field} changed"));
+            }
+        }
+        if !is_true(from, "deprecated") && is_true(to, "deprecated") {
+            self.push(
+                ChangeKind::Changed,
+                Element::Operation,
+                location,
+                "operation deprecated",
+                false,
+            );
+        }
+    }
+
+    fn parameters(
+        &mut self,
+        location: &str,
+        from: BTreeMap<String, &Value>,
+        to: BTreeMap<String, &Value>,
+    ) {
+        for (key, from_param) in &from {
+            let Some(to_param) = to.get(key) else {
+                self.push(
+                    ChangeKind::Removed,
+                    Element::Parameter,
+                    location,
+                    format!("parameter {key} removed"),
+                    true,
+                );
+                continue;
+            };
+
+            match (
+                is_true(from_param, "required"),
+                is_true(to_param, "required"),
+            ) {
+                (false, true) => self.push(
+                    ChangeKind::Changed,
+                    Element::Parameter,
+                    location,
+                    format!("parameter {key} became required"),
+                    true,
+                ),
+                (true, false) => self.push(
+                    ChangeKind::Changed,
+                    Element::Parameter,
+                    location,
+                    format!("parameter {key} became optional"),
+                    false,
+                ),
+                _ => (),
+            }
+            if from_param.get("description") != to_param.get("description") {
+                self.push_docs(
+                    Element::Parameter,
+                    location,
+                    format!("parameter {key} description changed"),
+                );
+            }
+            self.schema(
+                Element::Parameter,
+                location,
+                &format!("parameter {key}"),
+                Usage::REQUEST,
+                from_param.get("schema"),
+                to_param.get("schema"),
+            );
+        }
+
+        for (key, to_param) in to.iter().filter(|(key, _)| !from.contains_key(*key)) {
+            let required = is_true(to_param, "required");
+            self.push(
+                ChangeKind::Added,
+                Element::Parameter,
+                location,
+                format!(
+                    "{} parameter {key} added",
+                    if required { "required" } else { "optional" }
+                ),
+                required,
+            );
+        }
+    }
+
+    fn request_body(&mut self, location: &str, from: Option<&Value>, to: Option<&Value>) {
+        let (from, to) = match (from, to) {
+            (Some(_), None) => {
+                return self.push(
+                    ChangeKind::Removed,
+                    Element::RequestBody,
+                    location,
+                    "request body removed",
+                    true,
+                )
+            }
+            (None, Some(to)) => {
+                let required = is_true(to, "required");
+                return self.push(
+                    ChangeKind::Added,
+                    Element::RequestBody,
+                    location,
+                    format!(
+                        "{} request body added",
+                        if required { "required" } else { "optional" }
+                    ),
+                    required,
+                );
+            }
+            (Some(from), Some(to)) => (from, to),
+            (None, None) => return,
+        };
+
+        if let (Some(from_ref), Some(to_ref)) = (text_opt(from, "$ref"), text_opt(to, "$ref")) {
+            if from_ref != to_ref {
+                self.push(
+                    ChangeKind::Changed,
+                    Element::RequestBody,
+                    location,
+                    format!("request body changed from `{from_ref}` to `{to_ref}`"),
+                    true,
+                );
+            }
+            return;
+        }
+        if !is_true(from, "required") && is_true(to, "required") {
+            self.push(
+                ChangeKind::Changed,
+                Element::RequestBody,
+                location,
+                "request body became required",
+                true,
+            );
+        }
+        if from.get("description") != to.get("description") {
+            self.push_docs(
+                Element::RequestBody,
+                location,
+                "request body description changed",
+            );
+        }
+        self.content(
+            Element::RequestBody,
+            location,
+            "request body",
+            Usage::REQUEST,
+            object(from, "content"),
+            object(to, "content"),
+        );
+    }
+
+    fn responses(&mut self, location: &str, from: &Map<String, Value>, to: &Map<String, Value>) {
+        for (status, from_res) in from {
+            let Some(to_res) = to.get(status) else {
+                self.push(
+                    ChangeKind::Removed,
+                    Element::Response,
+                    location,
+                    format!("response {status} removed"),
+                    true,
+                );
+                continue;
+            };
+
+            if let (Some(from_ref), Some(to_ref)) =
+                (text_opt(from_res, "$ref"), text_opt(to_res, "$ref"))
+            {
+                if from_ref != to_ref {
+                    self.push(
+                        ChangeKind::Changed,
+                        Element::Response,
+                        location,
+                        format!("response {status} changed from `{from_ref}` to `{to_ref}`"),
+                        true,
+                    );
+                }
+                continue;
+            }
+            if from_res.get("description") != to_res.get("description") {
+                self.push_docs(
+                    Element::Response,
+                    location,
+                    format!("response {status} description changed"),
+                );
+            }
+            self.content(
+                Element::Response,
+                location,
+                &format!("response {status}"),
+                Usage::RESPONSE,
+                object(from_res, "content"),
+                object(to_res, "content"),
+            );
+        }
+
+        for status in to.keys().filter(|s| !from.contains_key(*s)) {
+            self.push(
+                ChangeKind::Added,
+                Element::Response,
+                location,
+                format!("response {status} added"),
+                false,
+            );
+        }
+    }
+
+    fn content(
+        &mut self,
+        element: Element,
+        location: &str,
+        field: &str,
+        usage: Usage,
+        from: &Map<String, Value>,
+        to: &Map<String, Value>,
+    ) {
+        for (media_type, from_media) in from {
+            match to.get(media_type) {
+                Some(to_media) => self.schema(
+                    element,
+                    location,
+                    &format!("{field} ({media_type})"),
+                    usage,
+                    from_media.get("schema"),
+                    to_media.get("schema"),
+                ),
+                None => self.push(
+                    ChangeKind::Removed,
+                    element,
+                    location,
+                    format!("{field} media type {media_type} removed"),
+                    true,
+                ),
+            }
+        }
+        for media_type in to.keys().filter(|m| !from.contains_key(*m)) {
+            self.push(
+                ChangeKind::Added,
+                element,
+                location,
+                format!("{field} media type {media_type} added"),
+                false,
+            );
+        }
+    }
+
+    fn schemas(&mut self, from: &Value, to: &Value) {
+        let (from_schemas, to_schemas) = (component_schemas(from), component_schemas(to));
+        for (name, from_schema) in from_schemas {
+            // schemas not used by any operation are still generated as sdk models
+            let usage = self
+                .usage
+                .get(name)
+                .copied()
+                .filter(|usage| *usage != Usage::default())
+                .unwrap_or(Usage::BOTH);
+            match to_schemas.get(name) {
+                Some(to_schema) => self.schema(
+                    Element::Schema,
+                    name,
+                    "",
+                    usage,
+                    Some(from_schema),
+                    Some(to_schema),
+                ),
+                None => self.push(
+                    ChangeKind::Removed,
+                    Element::Schema,
+                    name,
+                    "schema removed",
+                    true,
+                ),
+            }
+        }
+        for name in to_schemas.keys().filter(|n| !from_schemas.contains_key(*n)) {
+            self.push(
+                ChangeKind::Added,
+                Element::Schema,
+                name,
+                "schema added",
+                false,
+            );
+        }
+    }
+
+    /// compares two (possibly missing) schemas, `field` is prefixed to the details of
+    /// the changes e.g. `request body (application/json).owner`
+    fn schema(
+        &mut self,
+        element: Element,
+        location: &str,
+        field: &str,
+        usage: Usage,
+        from: Option<&Value>,
+        to: Option<&Value>,
+    ) {
+        let detail = |what: String| {
+            if field.is_empty() {
+                what
+            } else {
+                format!("{field}: {what}")
+            }
+        };
+        let (from, to) = match (from, to) {
+            (Some(from), Some(to)) => (from, to),
+            (None, None) => return,
+            (from, to) => {
+                return self.push(
+                    ChangeKind::Changed,
+                    element,
+                    location,
+                    detail(format!(
+                        "schema changed from {} to {}",
+                        describe(from),
+                        describe(to)
+                    )),
+                    true,
+                );
+            }
+        };
+
+        if from.get("$ref").is_some() || to.get("$ref").is_some() {
+            if from.get("$ref") != to.get("$ref") {
+                self.push(
+                    ChangeKind::Changed,
+                    element,
+                    location,
+                    detail(format!(
+                        "schema changed from {} to {}",
+                        describe(Some(from)),
+                        describe(Some(to))
+                    )),
+                    true,
+                );
+            }
+            return;
+        }
+
+        for field in ["type", "format"] {
+            if from.get(field) != to.get(field) {
+                self.push(
+                    ChangeKind::Changed,
+                    element,
+                    location,
+                    detail(format!(
+                        "{field} changed from {} to {}",
+                        display(from.get(field)),
+                        display(to.get(field))
+                    )),
+                    true,
+                );
+            }
+        }
+        if from.get("description") != to.get("description") {
+            self.push_docs(element, location, detail("description changed".into()));
+        }
+        match (is_true(from, "nullable"), is_true(to, "nullable")) {
+            // clients may not handle null values read from responses
+            (false, true) => self.push(
+                ChangeKind::Changed,
+                element,
+                location,
+                detail("became nullable".into()),
+                usage.breaks(false, true),
+            ),
+            (true, false) => self.push(
+                ChangeKind::Changed,
+                element,
+                location,
+                detail("no longer nullable".into()),
+                usage.breaks(true, false),
+            ),
+            _ => (),
+        }
+
+        // requests may no longer send removed values, responses may return new ones
+        let (from_enum, to_enum) = (array(from, "enum"), array(to, "enum"));
+        for val in from_enum.iter().filter(|v| !to_enum.contains(v)) {
+            self.push(
+                ChangeKind::Removed,
+                element,
+                location,
+                detail(format!("enum value {} removed", display(Some(val)))),
+                usage.breaks(true, false),
+            );
+        }
+        for val in to_enum.iter().filter(|v| !from_enum.contains(v)) {
+            self.push(
+                ChangeKind::Added,
+                element,
+                location,
+                detail(format!("enum value {} added", display(Some(val)))),
+                usage.breaks(false, true),
+            );
+        }
+
+        let (from_required, to_required) = (array(from, "required"), array(to, "required"));
+        let (from_props, to_props) = (object(from, "properties"), object(to, "properties"));
+        let prop_field = |prop: &str| {
+            if field.is_empty() {
+                prop.to_string()
+            } else {
+                format!("{field}.{prop}")
+            }
+        };
+        for (prop, from_prop) in from_props {
+            let Some(to_prop) = to_props.get(prop) else {
+                self.push(
+                    ChangeKind::Removed,
+                    element,
+                    location,
+                    detail(format!("property `{prop}` removed")),
+                    true,
+                );
+                continue;
+            };
+
+            let key = Value::String(prop.clone());
+            match (from_required.contains(&key), to_required.contains(&key)) {
+                (false, true) => self.push(
+                    ChangeKind::Changed,
+                    element,
+                    location,
+                    detail(format!("property `{prop}` became required")),
+                    usage.breaks(true, false),
+                ),
+                (true, false) => self.push(
+                    ChangeKind::Changed,
+                    element,
+                    location,
+                    detail(format!("property `{prop}` became optional")),
+                    usage.breaks(false, true),
+                ),
+                _ => (),
+            }
+            self.schema(
+                element,
+                location,
+                &prop_field(prop),
+                usage,
+                Some(from_prop),
+                Some(to_prop),
+            );
+        }
+        for prop in to_props.keys().filter(|p| !from_props.contains_key(*p)) {
+            let required = to_required.contains(&Value::String(prop.clone()));
+            self.push(
+                ChangeKind::Added,
+                element,
+                location,
+                detail(format!(
+                    "{} property `{prop}` added",
+                    if required { "required" } else { "optional" }
+                )),
+                required && usage.breaks(true, false),
+            );
+        }
+
+        match (AdditionalProperties::of(from), AdditionalProperties::of(to)) {
+            (AdditionalProperties::Schema(from_extra), AdditionalProperties::Schema(to_extra)) => {
+                self.schema(
+                    element,
+                    location,
+                    &prop_field("*"),
+                    usage,
+                    Some(from_extra),
+                    Some(to_extra),
+                )
+            }
+            (from_extra, to_extra) if from_extra.rank() < to_extra.rank() => self.push(
+                ChangeKind::Changed,
+                element,
+                location,
+                detail(format!(
+                    "additional properties changed from {} to {}",
+                    from_extra.describe(),
+                    to_extra.describe()
+                )),
+                usage.breaks(false, true),
+            ),
+            (from_extra, to_extra) if from_extra.rank() > to_extra.rank() => self.push(
+                ChangeKind::Changed,
+                element,
+                location,
+                detail(format!(
+                    "additional properties changed from {} to {}",
+                    from_extra.describe(),
+                    to_extra.describe()
+                )),
+                usage.breaks(true, false),
+            ),
+            _ => (),
+        }
+
+        if from.get("items").is_some() || to.get("items").is_some() {
+            self.schema(
+                element,
+                location,
+                &format!("{}[]", if field.is_empty() { "items" } else { field }),
+                usage,
+                from.get("items"),
+                to.get("items"),
+            );
+        }
+
+        // every `allOf` member constrains the schema further, while `oneOf` and `anyOf`
+        // members are alternatives
+        for (keyword, narrows) in [("allOf", true), ("oneOf", false), ("anyOf", false)] {
+            let (from_members, to_members) = (members(from, keyword), members(to, keyword));
+            for (key, from_member) in &from_members {
+                match to_members.get(key) {
+                    Some(to_member) => self.schema(
+                        element,
+                        location,
+                        &prop_field(&format!("{keyword}[{key}]")),
+                        usage,
+                        Some(from_member),
+                        Some(to_member),
+                    ),
+                    None => self.push(
+                        ChangeKind::Removed,
+                        element,
+                        location,
+                        detail(format!("{keyword} member {key} removed")),
+                        if narrows {
+                            usage.breaks(false, true)
+                        } else {
+                            usage.breaks(true, false)
+                        },
+                    ),
+                }
+            }
+            for key in to_members.keys().filter(|k| !from_members.contains_key(*k)) {
+                self.push(
+                    ChangeKind::Added,
+                    element,
+                    location,
+                    detail(format!("{keyword} member {key} added")),
+                    if narrows {
+                        usage.breaks(true, false)
+                    } else {
+                        usage.breaks(false, true)
+                    },
+                );
+            }
+        }
+    }
+}
+
+/// directions a schema is used in, a change may break clients sending the schema in
+/// requests but not clients reading it from responses or vice versa
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct Usage {
+    request: bool,
+    response: bool,
+}
+
+impl Usage {
+    const REQUEST: Usage = Usage {
+        request: true,
+        response: false,
+    };
+    const RESPONSE: Usage = Usage {
+        request: false,
+        response: true,
+    };
+    const BOTH: Usage = Usage {
+        request: true,
+        response: true,
+    };
+
+    fn union(self, other: Usage) -> Usage {
+        Usage {
+            request: self.request || other.request,
+            response: self.response || other.response,
+        }
+    }
+
+    /// whether a change breaks clients, given whether it does so for schemas sent in
+    /// requests and for schemas read from responses
+    fn breaks(self, in_request: bool, in_response: bool) -> bool {
+        (self.request && in_request) || (self.response && in_response)
+    }
+}
+
+/// `additionalProperties` of an object schema, allowing any value when absent
+enum AdditionalProperties<'a> {
+    Forbidden,
+    Schema(&'a Value),
+    Any,
+}
+
+impl<'a> AdditionalProperties<'a> {
+    fn of(schema: &'a Value) -> Self {
+        match schema.get("additionalProperties") {
+            Some(Value::Bool(false)) => Self::Forbidden,
+            Some(Value::Bool(true)) | None => Self::Any,
+            Some(schema) => Self::Schema(schema),
+        }
+    }
+
+    /// how many additional properties are accepted, relative to the other variants
+    fn rank(&self) -> u8 {
+        match self {
+            Self::Forbidden => 0,
+            Self::Schema(_) => 1,
+            Self::Any => 2,
+        }
+    }
+
+    fn describe(&self) -> String {
+        match self {
+            Self::Forbidden => "forbidden".into(),
+            Self::Schema(schema) => describe(Some(schema)),
+            Self::Any => "any".into(),
+        }
+    }
+}
+
+/// members of an `allOf`, `oneOf` or `anyOf` keyed by their reference, or their
+/// position for inline schemas
+fn members<'a>(schema: &'a Value, keyword: &str) -> BTreeMap<String, &'a Value> {
+    array(schema, keyword)
+        .iter()
+        .enumerate()
+        .map(|(i, member)| match text_opt(member, "$ref") {
+            Some(reference) => (format!("`{reference}`"), member),
+            None => (i.to_string(), member),
+        })
+        .collect()
+}
+
+/// component schemas referenced by the requests and responses of the operations, following
+/// references between schemas
+fn schema_usage(spec: &Value) -> BTreeMap<String, Usage> {
+    let mut refs = BTreeMap::new();
+    for item in object(spec, "paths").values() {
+        for operation in METHODS.iter().filter_map(|m| item.get(m)) {
+            for param in parameters(item, operation).into_values() {
+                collect_refs(spec, param, Usage::REQUEST, &mut refs);
+            }
+            if let Some(body) = operation.get("requestBody") {
+                collect_refs(spec, body, Usage::REQUEST, &mut refs);
+            }
+            if let Some(responses) = operation.get("responses") {
+                collect_refs(spec, responses, Usage::RESPONSE, &mut refs);
+            }
+        }
+    }
+
+    let prefix = if spec.get("components").is_some() {
+        "#/components/schemas/"
+    } else {
+        "#/definitions/"
+    };
+    refs.into_iter()
+        .filter_map(|(reference, usage)| {
+            let name = reference.strip_prefix(prefix)?;
+            Some((name.replace("~1", "/").replace("~0", "~"), usage))
+        })
+        .collect()
+}
+
+/// records the usage of the references within the value, following local references into
+/// the spec unless they were already followed in the same direction
+fn collect_refs(spec: &Value, val: &Value, usage: Usage, refs: &mut BTreeMap<String, Usage>) {
+    match val {
+        Value::Object(map) => {
+            if let Some(reference) = map.get("$ref").and_then(Value::as_str) {
+                let known = refs.entry(reference.to_string()).or_default();
+                let followed = known.union(usage) == *known;
+                *known = known.union(usage);
+                if !followed {
+                    if let Some(target) = reference.strip_prefix('#').and_then(|p| spec.pointer(p))
+                    {
+                        collect_refs(spec, target, usage, refs);
+                    }
+                }
+            }
+            for val in map.values() {
+                collect_refs(spec, val, usage, refs);
+            }
+        }
+        Value::Array(items) => {
+            for val in items {
+                collect_refs(spec, val, usage, refs);
+            }
+        }
+        _ => (),
+    }
+}
+
+/// path level parameters overridden by operation parameters, keyed by name and location
+fn parameters<'a>(item: &'a Value, operation: &'a Value) -> BTreeMap<String, &'a Value> {
+    [item, operation]
+        .into_iter()
+        .filter_map(|v| v.get("parameters").and_then(Value::as_array))
+        .flatten()
+        .map(|param| {
+            let key = match text_opt(param, "$ref") {
+                Some(reference) => format!("`{reference}`"),
+                None => format!("`{}` ({})", text(param, "name"), text(param, "in")),
+            };
+            (key, param)
+        })
+        .collect()
+}
+
+/// `components.schemas` of openapi 3 or `definitions` of swagger 2
+fn component_schemas(spec: &Value) -> &Map<String, Value> {
+    match spec.get("components") {
+        Some(components) => object(components, "schemas"),
+        None => object(spec, "definitions"),
+    }
+}
+
+fn object<'a>(val: &'a Value, key: &str) -> &'a Map<String, Value> {
+    static EMPTY: std::sync::OnceLock<Map<String, Value>> = std::sync::OnceLock::new();
+    val.get(key)
+        .and_then(Value::as_object)
+        .unwrap_or_else(|| EMPTY.get_or_init(Map::new))
+}
+
+fn array<'a>(val: &'a Value, key: &str) -> &'a [Value] {
+    val.get(key)
+        .and_then(Value::as_array)
+        .map(Vec::as_slice)
+        .unwrap_or_default()
+}
+
+fn text_opt<'a>(val: &'a Value, key: &str) -> Option<&'a str> {
+    val.get(key).and_then(Value::as_str)
+}
+
+fn text<'a>(val: &'a Value, key: &str) -> &'a str {
+    text_opt(val, key).unwrap_or_default()
+}
+
+fn is_true(val: &Value, key: &str) -> bool {
+    val.get(key).and_then(Value::as_bool).unwrap_or_default()
+}
+
+fn display(val: Option<&Value>) -> String {
+    match val {
+        None | Some(Value::Null) => "none".into(),
+        Some(Value::String(s)) => format!("`{s}`"),
+        Some(val) => format!("`{val}`"),
+    }
+}
+
+/// short description of a schema for change details, e.g. `#/components/schemas/Pet`
+fn describe(schema: Option<&Value>) -> String {
+    match schema {
+        None => "none".into(),
+        Some(schema) => match text_opt(schema, "$ref") {
+            Some(reference) => format!("`{reference}`"),
+            None => display(schema.get("type")),
+        },
+    }
+}
+
+/// semantic version bump implied by a set of changes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Bump {
+    Patch,
+    Minor,
+    Major,
+}
+
+impl Bump {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Bump::Patch => "patch",
+            Bump::Minor => "minor",
+            Bump::Major => "major",
+        }
+    }
+
+    /// breaking changes require a major, additive changes a minor and documentation
+    /// changes a patch bump, along with the reasons for each
+    pub fn infer(changes: &[Change]) -> (Self, Vec<String>) {
+        let classes = [
+            (
+                Bump::Major,
+                "breaking",
+                changes.iter().filter(|c| c.breaking).collect::<Vec<_>>(),
+            ),
+            (
+                Bump::Minor,
+                "additive",
+                changes.iter().filter(|c| !c.breaking && !c.docs).collect(),
+            ),
+            (
+                Bump::Patch,
+                "documentation",
+                changes.iter().filter(|c| c.docs).collect(),
+            ),
+        ];
+
+        let mut bump = Bump::Patch;
+        let mut reasons = vec![];
+        for (class_bump, class, class_changes) in classes {
+            let Some(example) = class_changes.first() else {
+                continue;
+            };
+            bump = bump.max(class_bump);
+            reasons.push(format!(
+                "{count} {class} change{s} → {bump} (e.g. {location}: {detail})",
+                count = class_changes.len(),
+                s = if class_changes.len() == 1 { "" } else { "s" },
+                bump = class_bump.as_str(),
+                location = &example.location,
+                detail = &example.detail,
+            ));
+        }
+        if reasons.is_empty() {
+            reasons.push("no changes → patch".into());
+        }
+
+        (bump, reasons)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Value};
+
+    use super::*;
+
+    /// spec with a `Pet` schema sent by `POST /pets` and read from `GET /pets/{id}`,
+    /// and a `Tag` schema only read from `GET /tags`
+    fn spec(pet: Value, tag: Value) -> Value {
+        let pet_ref = json!({ "$ref": "#/components/schemas/Pet" });
+        let tag_ref = json!({ "$ref": "#/components/schemas/Tag" });
+        json!({
+            "openapi": "3.0.0",
+            "paths": {
+                "/pets": {
+                    "post": {
+                        "requestBody": { "content": { "application/json": { "schema": pet_ref } } },
+                        "responses": { "204": { "description": "created" } }
+                    }
+                },
+                "/pets/{id}": {
+                    "get": {
+                        "responses": {
+                            "200": {
+                                "description": "pet",
+                                "content": { "application/json": { "schema": pet_ref } }
+                            }
+                        }
+                    }
+                },
+                "/tags": {
+                    "get": {
+                        "responses": {
+                            "200": {
+                                "description": "tags",
+                                "content": { "application/json": { "schema": { "type": "array", "items": tag_ref } } }
+                            }
+                        }
+                    }
+                }
+            },
+            "components": { "schemas": { "Pet": pet, "Tag": tag } }
+        })
+    }
+
+    /// changes to the `Tag` schema, which is only read from responses
+    fn response_changes(from: Value, to: Value) -> Vec<Change> {
+        let pet = json!({ "type": "object" });
+        diff(&spec(pet.clone(), from), &spec(pet, to))
+    }
+
+    /// changes of a schema used only in a request body
+    fn request_changes(from: Value, to: Value) -> Vec<Change> {
+        let wrap = |schema: Value| {
+            json!({
+                "paths": {
+                    "/pets": {
+                        "post": {
+                            "requestBody": { "content": { "application/json": { "schema": schema } } },
+                            "responses": {}
+                        }
+                    }
+                }
+            })
+        };
+        diff(&wrap(from), &wrap(to))
+    }
+
+    fn only(changes: &[Change]) -> &Change {
+        assert_eq!(changes.len(), 1, "{changes:#?}");
+        &changes[0]
+    }
+
+    #[test]
+    fn classifies_schemas_reached_from_requests_and_responses() {
+        let from = json!({ "type": "object", "properties": { "name": { "type": "string" } } });
+        let to = json!({
+            "type": "object",
+            "properties": { "name": { "type": "string" } },
+            "required": ["name"]
+        });
+
+        let mut usage = schema_usage(&spec(from.clone(), from.clone()));
+        assert_eq!(usage.remove("Pet"), Some(Usage::BOTH));
+        assert_eq!(usage.remove("Tag"), Some(Usage::RESPONSE));
+
+        // `Pet` is sent in requests as well, so it breaks
+        assert!(
+            only(&diff(
+                &spec(from.clone(), json!({})),
+                &spec(to.clone(), json!({}))
+            ))
+            .breaking
+        );
+        // `Tag` is only read, guaranteeing the property is fine
+        assert!(!only(&response_changes(from.clone(), to.clone())).breaking);
+        assert!(only(&request_changes(from, to)).breaking);
+    }
+
+    #[test]
+    fn follows_references_between_schemas() {
+        let mut spec = spec(
+            json!({ "type": "object" }),
+            json!({ "$ref": "#/components/schemas/Name" }),
+        );
+        spec["components"]["schemas"]["Name"] = json!({ "type": "string" });
+
+        assert_eq!(schema_usage(&spec).get("Name"), Some(&Usage::RESPONSE));
+    }
+
+    #[test]
+    fn treats_unused_schemas_as_both_directions() {
+        let from =
+            json!({ "paths": {}, "components": { "schemas": { "Pet": { "enum": ["a"] } } } });
+        let to =
+            json!({ "paths": {}, "components": { "schemas": { "Pet": { "enum": ["a", "b"] } } } });
+
+        assert!(only(&diff(&from, &to)).breaking);
+    }
+
+    #[test]
+    fn classifies_enum_values_by_direction() {
+        let (from, to) = (json!({ "enum": ["a", "b"] }), json!({ "enum": ["a", "c"] }));
+
+        let changes = request_changes(from.clone(), to.clone());
+        assert_eq!(changes.len(), 2);
+        assert!(changes
+            .iter()
+            .all(|c| c.breaking == (c.kind == ChangeKind::Removed)));
+
+        let changes = response_changes(from, to);
+        assert_eq!(changes.len(), 2);
+        assert!(changes
+            .iter()
+            .all(|c| c.breaking == (c.kind == ChangeKind::Added)));
+    }
+
+    #[test]
+    fn classifies_optional_properties_by_direction() {
+        let from = json!({ "properties": { "name": { "type": "string" } }, "required": ["name"] });
+        let to = json!({ "properties": { "name": { "type": "string" } } });
+
+        assert!(!only(&request_changes(from.clone(), to.clone())).breaking);
+        assert!(only(&response_changes(from, to)).breaking);
+    }
+
+    #[test]
+    fn classifies_added_properties() {
+        let from = json!({ "properties": {} });
+        let optional = json!({ "properties": { "name": { "type": "string" } } });
+        let required =
+            json!({ "properties": { "name": { "type": "string" } }, "required": ["name"] });
+
+        assert!(!only(&request_changes(from.clone(), optional.clone())).breaking);
+        assert!(only(&request_changes(from.clone(), required.clone())).breaking);
+        assert!(!only(&response_changes(from.clone(), optional)).breaking);
+        assert!(!only(&response_changes(from, required)).breaking);
+    }
+
+    #[test]
+    fn classifies_nullable_by_direction() {
+        let (from, to) = (
+            json!({ "type": "string" }),
+            json!({ "type": "string", "nullable": true }),
+        );
+
+        assert!(!only(&request_changes(from.clone(), to.clone())).breaking);
+        assert!(only(&response_changes(from.clone(), to.clone())).breaking);
+        assert!(only(&request_changes(to.clone(), from.clone())).breaking);
+        assert!(!only(&response_changes(to, from)).breaking);
+    }
+
+    #[test]
+    fn classifies_additional_properties_by_direction() {
+        let forbidden = json!({ "type": "object", "additionalProperties": false });
+        let any = json!({ "type": "object" });
+
+        assert!(only(&request_changes(any.clone(), forbidden.clone())).breaking);
+        assert!(!only(&response_changes(any.clone(), forbidden.clone())).breaking);
+        assert!(!only(&request_changes(forbidden.clone(), any.clone())).breaking);
+        assert!(only(&response_changes(forbidden, any)).breaking);
+
+        let strings = json!({ "type": "object", "additionalProperties": { "type": "string" } });
+        let ints = json!({ "type": "object", "additionalProperties": { "type": "integer" } });
+        let change = only(&request_changes(strings, ints)).clone();
+        assert!(change.breaking);
+        assert!(
+            change
+                .detail
+                .starts_with("request body (application/json).*:"),
+            "{}",
+            change.detail
+        );
+    }
+
+    #[test]
+    fn classifies_composition_members_by_direction() {
+        let cat = json!({ "$ref": "#/components/schemas/Cat" });
+        let dog = json!({ "$ref": "#/components/schemas/Dog" });
+        let one = json!({ "oneOf": [cat.clone()] });
+        let two = json!({ "oneOf": [cat.clone(), dog.clone()] });
+
+        // a new alternative may be returned, but sending the old one still works
+        assert!(!only(&request_changes(one.clone(), two.clone())).breaking);
+        assert!(only(&response_changes(one.clone(), two.clone())).breaking);
+        assert!(only(&request_changes(two, one)).breaking);
+
+        // a new `allOf` member is another constraint on requests
+        let all_one = json!({ "allOf": [cat.clone()] });
+        let all_two = json!({ "allOf": [cat, dog] });
+        assert!(only(&request_changes(all_one.clone(), all_two.clone())).breaking);
+        assert!(!only(&response_changes(all_one, all_two)).breaking);
+
+        // inline members are compared by position
+        let from = json!({ "anyOf": [{ "type": "string" }] });
+        let to = json!({ "anyOf": [{ "type": "integer" }] });
+        let change = only(&request_changes(from, to)).clone();
+        assert!(change.breaking);
+        assert!(change.detail.contains("anyOf[0]"), "{}", change.detail);
+    }
+
+    #[test]
+    fn reports_documentation_changes_as_non_breaking() {
+        let changes = response_changes(
+            json!({ "type": "string", "description": "a tag" }),
+            json!({ "type": "string", "description": "the tag" }),
+        );
+        let change = only(&changes);
+
+        assert!(change.docs);
+        assert!(!change.breaking);
+        assert_eq!(Bump::infer(&changes).0, Bump::Patch);
+    }
+}
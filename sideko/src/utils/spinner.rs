@@ -1,5 +1,8 @@
-use crate::styles::{fmt_green, fmt_red, fmt_yellow};
-use log::{error, info, log_enabled, warn};
+use crate::{
+    styles::{fmt_green, fmt_red, fmt_yellow, stderr_color},
+    utils::logging,
+};
+use log::{error, info, warn};
 use spinoff::*;
 use std::borrow::Cow;
 
@@ -21,27 +24,38 @@ impl SidekoSpinner {
 }
 
 /// Wrapper around spinoff::Spinner to handle only
-/// showing if log level is INFO
+/// showing if log level is INFO and colors are enabled,
+/// otherwise progress is logged as lines
 pub struct Spinner {
     sp: Option<spinoff::Spinner>,
 }
 
 impl Spinner {
     pub fn new<M: Into<Cow<'static, str>>>(msg: M) -> Self {
-        let sp = if log_enabled!(log::Level::Debug) || !log_enabled!(log::Level::Info) {
-            // level debug or quiet mode
+        let sp = if logging::terminal_enabled(log::Level::Debug)
+            || !logging::terminal_enabled(log::Level::Info)
+            || !stderr_color()
+        {
+            // level debug, quiet mode or no colors (e.g. not a terminal)
             info!("{}...", msg.into());
             None
         } else {
-            Some(spinoff::Spinner::new(
+            // stdout is reserved for command results
+            Some(spinoff::Spinner::new_with_stream(
                 SidekoSpinner::frames(),
                 msg,
                 Color::Green,
+                Streams::Stderr,
             ))
         };
         Self { sp }
     }
 
+    /// whether the spinner is shown, otherwise messages are logged
+    pub fn is_animated(&self) -> bool {
+        self.sp.is_some()
+    }
+
     pub fn update_text<M: Into<Cow<'static, str>>>(&mut self, msg: M) {
         if let Some(sp) = self.sp.as_mut() {
             sp.update_text(msg);
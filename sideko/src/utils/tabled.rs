@@ -1,7 +1,20 @@
 use tabled::{
-    settings::{object::Rows, themes::Colorization, Alignment, Color, Padding, Panel, Remove},
+    settings::{
+        object::Rows, peaker::Priority, themes::Colorization, Alignment, Color, Padding, Panel,
+        Remove, Style, Width,
+    },
     Table,
 };
+use terminal_size::{terminal_size, Height as TerminalHeight, Width as TerminalWidth};
+
+/// consistent table format that fits in existing terminal size
+pub fn fit_table(table: &mut Table) {
+    table.with(Style::modern());
+
+    if let Some((TerminalWidth(width), TerminalHeight(_height))) = terminal_size() {
+        table.with(Width::wrap(width as usize).priority(Priority::max(true)));
+    }
+}
 
 pub fn header_panel(table: &mut Table, text: &str) {
     table
@@ -0,0 +1,563 @@
+use serde_json::Value;
+
+use crate::result::{CliError, CliResult, ErrorCode};
+
+use super::output;
+
+/// go template style output format, e.g. `{{.name}} {{.version}}`
+///
+/// supported actions are field paths (`{{.mock_server.url}}`, `{{.}}`), pipes
+/// (`{{.name | upper}}`), the functions `json`, `upper`, `lower`, `len`, `join` and
+/// `index`, as well as `{{if}}`, `{{range}}`, `{{else}}`, `{{else if}}` and `{{end}}`.
+/// missing or null fields render as an empty string
+#[derive(Debug, Clone)]
+pub struct Template {
+    nodes: Vec<Node>,
+}
+
+#[derive(Debug, Clone)]
+enum Node {
+    Text(String),
+    Action(Pipeline),
+    If {
+        cond: Pipeline,
+        then: Vec<Node>,
+        otherwise: Vec<Node>,
+    },
+    Range {
+        over: Pipeline,
+        body: Vec<Node>,
+        otherwise: Vec<Node>,
+    },
+}
+
+/// commands separated by `|`, the result of each command is passed as the last
+/// argument of the next one
+#[derive(Debug, Clone)]
+struct Pipeline(Vec<Command>);
+
+#[derive(Debug, Clone)]
+struct Command {
+    func: Option<Func>,
+    args: Vec<Arg>,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Func {
+    Json,
+    Upper,
+    Lower,
+    Len,
+    Join,
+    Index,
+}
+
+#[derive(Debug, Clone)]
+enum Arg {
+    /// field path relative to the current value, empty for `.`
+    Path(Vec<String>),
+    Str(String),
+    Int(i64),
+}
+
+/// a parsed `{{ ... }}` block or the text between blocks
+enum Token {
+    Text(String),
+    Action(String),
+}
+
+impl Template {
+    /// parses a template, used as a clap value parser
+    pub fn parse(template: &str) -> Result<Self, String> {
+        let mut tokens = tokenize(template)?.into_iter();
+        let (nodes, end) = parse_nodes(&mut tokens)?;
+        match end {
+            None => Ok(Self { nodes }),
+            Some(keyword) => Err(format!("unexpected {{{{{keyword}}}}}")),
+        }
+    }
+
+    pub fn render(&self, val: &Value) -> CliResult<String> {
+        let mut out = String::new();
+        render_nodes(&self.nodes, val, &mut out).map_err(|e| {
+            CliError::general(format!("failed rendering --format template: {e}"))
+                .with_code(ErrorCode::InvalidInput)
+        })?;
+
+        Ok(out)
+    }
+
+    /// prints the rendered template for each item of a list, or once for anything else
+    pub fn print<T: ?Sized + serde::Serialize>(&self, val: &T) -> CliResult<()> {
+        let val = serde_json::to_value(val)
+            .map_err(|e| CliError::general_debug("failed serializing result", e))?;
+        match &val {
+            Value::Array(items) => {
+                for item in items {
+                    output::print_line(self.render(item)?);
+                }
+            }
+            val => output::print_line(self.render(val)?),
+        }
+
+        Ok(())
+    }
+}
+
+/// splits the template into text and actions, `\n` and `\t` in text are unescaped as
+/// shells do not do it for arguments
+fn tokenize(template: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = vec![];
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        if start > 0 {
+            tokens.push(Token::Text(unescape(&rest[..start])));
+        }
+        let action = &rest[start + 2..];
+        let end = action
+            .find("}}")
+            .ok_or_else(|| format!("unclosed action `{{{{{action}`"))?;
+        tokens.push(Token::Action(action[..end].trim().to_string()));
+        rest = &action[end + 2..];
+    }
+    if !rest.is_empty() {
+        tokens.push(Token::Text(unescape(rest)));
+    }
+
+    Ok(tokens)
+}
+
+fn unescape(text: &str) -> String {
+    text.replace("\\n", "\n").replace("\\t", "\t")
+}
+
+/// parses nodes until the end of input or a closing `else`, `else if` or `end`, which is
+/// returned
+fn parse_nodes(
+    tokens: &mut impl Iterator<Item = Token>,
+) -> Result<(Vec<Node>, Option<String>), String> {
+    let mut nodes = vec![];
+    while let Some(token) = tokens.next() {
+        let action = match token {
+            Token::Text(text) => {
+                nodes.push(Node::Text(text));
+                continue;
+            }
+            Token::Action(action) => action,
+        };
+
+        let (keyword, rest) = action.split_once(' ').unwrap_or((&action, ""));
+        let rest = rest.trim();
+        match keyword {
+            "else" if rest.is_empty() || rest.starts_with("if ") => {
+                return Ok((nodes, Some(action.clone())))
+            }
+            "end" if rest.is_empty() => return Ok((nodes, Some(action.clone()))),
+            "else" | "end" => return Err(format!("unexpected `{rest}` in {{{{{keyword}}}}}")),
+            "if" | "range" => {
+                let pipeline = parse_pipeline(rest)?;
+                let (body, end) = parse_nodes(tokens)?;
+                let otherwise = parse_otherwise(tokens, &action, end)?;
+                nodes.push(if keyword == "if" {
+                    Node::If {
+                        cond: pipeline,
+                        then: body,
+                        otherwise,
+                    }
+                } else {
+                    Node::Range {
+                        over: pipeline,
+                        body,
+                        otherwise,
+                    }
+                });
+            }
+            _ => nodes.push(Node::Action(parse_pipeline(&action)?)),
+        }
+    }
+
+    Ok((nodes, None))
+}
+
+/// parses the `else` branch of the `if` or `range` action up to its `end`, an `else if`
+/// is nested as an `if` in the branch sharing the same `end`
+fn parse_otherwise(
+    tokens: &mut impl Iterator<Item = Token>,
+    action: &str,
+    end: Option<String>,
+) -> Result<Vec<Node>, String> {
+    let missing_end = || format!("missing {{{{end}}}} of {{{{{action}}}}}");
+    let Some(end) = end else {
+        return Err(missing_end());
+    };
+    if end == "end" {
+        return Ok(vec![]);
+    }
+    if end == "else" {
+        return match parse_nodes(tokens)? {
+            (otherwise, Some(end)) if end == "end" => Ok(otherwise),
+            (_, Some(end)) => Err(format!("unexpected {{{{{end}}}}} after {{{{else}}}}")),
+            (_, None) => Err(missing_end()),
+        };
+    }
+
+    let cond = end.trim_start_matches("else").trim();
+    let pipeline = parse_pipeline(cond.trim_start_matches("if").trim())?;
+    let (then, next) = parse_nodes(tokens)?;
+    let otherwise = parse_otherwise(tokens, action, next)?;
+
+    Ok(vec![Node::If {
+        cond: pipeline,
+        then,
+        otherwise,
+    }])
+}
+
+fn parse_pipeline(pipeline: &str) -> Result<Pipeline, String> {
+    let commands = split_outside_quotes(pipeline, '|')
+        .iter()
+        .map(|cmd| parse_command(cmd))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(Pipeline(commands))
+}
+
+fn parse_command(command: &str) -> Result<Command, String> {
+    let words = split_outside_quotes(command, ' ');
+    let mut words = words.iter().map(|w| w.trim()).filter(|w| !w.is_empty());
+    let Some(first) = words.next() else {
+        return Err("empty action".into());
+    };
+
+    let (func, args) = match first {
+        "json" => (Some(Func::Json), vec![]),
+        "upper" => (Some(Func::Upper), vec![]),
+        "lower" => (Some(Func::Lower), vec![]),
+        "len" => (Some(Func::Len), vec![]),
+        "join" => (Some(Func::Join), vec![]),
+        "index" => (Some(Func::Index), vec![]),
+        arg => (None, vec![parse_arg(arg)?]),
+    };
+    let mut args = args;
+    for word in words {
+        args.push(parse_arg(word)?);
+    }
+    if func.is_none() && args.len() > 1 {
+        return Err(format!("unexpected arguments in `{command}`"));
+    }
+
+    Ok(Command { func, args })
+}
+
+fn parse_arg(arg: &str) -> Result<Arg, String> {
+    if let Some(path) = arg.strip_prefix('.') {
+        return Ok(Arg::Path(
+            path.split('.')
+                .filter(|p| !p.is_empty())
+                .map(String::from)
+                .collect(),
+        ));
+    }
+    if let Some(s) = arg.strip_prefix('"').and_then(|a| a.strip_suffix('"')) {
+        return Ok(Arg::Str(s.replace("\\\"", "\"")));
+    }
+    if let Ok(i) = arg.parse::<i64>() {
+        return Ok(Arg::Int(i));
+    }
+
+    Err(format!("unknown function or argument `{arg}`"))
+}
+
+/// splits on the separator unless it is inside a double quoted string
+fn split_outside_quotes(text: &str, sep: char) -> Vec<String> {
+    let mut parts = vec![String::new()];
+    let mut quoted = false;
+    let mut escaped = false;
+    for c in text.chars() {
+        match c {
+            '"' if !escaped => quoted = !quoted,
+            c if c == sep && !quoted => {
+                parts.push(String::new());
+                continue;
+            }
+            _ => (),
+        }
+        escaped = c == '\\' && !escaped;
+        if let Some(part) = parts.last_mut() {
+            part.push(c);
+        }
+    }
+
+    parts
+}
+
+fn render_nodes(nodes: &[Node], dot: &Value, out: &mut String) -> Result<(), String> {
+    for node in nodes {
+        match node {
+            Node::Text(text) => out.push_str(text),
+            Node::Action(pipeline) => out.push_str(&display(&eval(pipeline, dot)?)),
+            Node::If {
+                cond,
+                then,
+                otherwise,
+            } => {
+                let branch = if is_truthy(&eval(cond, dot)?) {
+                    then
+                } else {
+                    otherwise
+                };
+                render_nodes(branch, dot, out)?;
+            }
+            Node::Range {
+                over,
+                body,
+                otherwise,
+            } => {
+                let items: Vec<Value> = match eval(over, dot)? {
+                    Value::Array(items) => items,
+                    Value::Object(map) => map.into_iter().map(|(_, v)| v).collect(),
+                    Value::Null => vec![],
+                    val => return Err(format!("cannot range over {val}")),
+                };
+                if items.is_empty() {
+                    render_nodes(otherwise, dot, out)?;
+                }
+                for item in &items {
+                    render_nodes(body, item, out)?;
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn eval(pipeline: &Pipeline, dot: &Value) -> Result<Value, String> {
+    let mut piped: Option<Value> = None;
+    for command in &pipeline.0 {
+        let mut args = command
+            .args
+            .iter()
+            .map(|arg| match arg {
+                Arg::Path(path) => lookup(dot, path),
+                Arg::Str(s) => Value::String(s.clone()),
+                Arg::Int(i) => Value::from(*i),
+            })
+            .collect::<Vec<_>>();
+        args.extend(piped.take());
+
+        piped = Some(match command.func {
+            None => args.pop().unwrap_or(Value::Null),
+            Some(func) => call(func, &args)?,
+        });
+    }
+
+    Ok(piped.unwrap_or(Value::Null))
+}
+
+fn call(func: Func, args: &[Value]) -> Result<Value, String> {
+    let arity = match func {
+        Func::Join | Func::Index => 2,
+        _ => 1,
+    };
+    if args.len() != arity {
+        return Err(
+            format!("{func:?} expects {arity} argument(s), got {}", args.len()).to_lowercase(),
+        );
+    }
+
+    let val = match func {
+        Func::Json => Value::String(args[0].to_string()),
+        Func::Upper => Value::String(display(&args[0]).to_uppercase()),
+        Func::Lower => Value::String(display(&args[0]).to_lowercase()),
+        Func::Len => Value::from(match &args[0] {
+            Value::Array(items) => items.len(),
+            Value::Object(map) => map.len(),
+            Value::String(s) => s.chars().count(),
+            Value::Null => 0,
+            val => return Err(format!("len of {val} is not defined")),
+        }),
+        Func::Join => match &args[1] {
+            Value::Array(items) => Value::String(
+                items
+                    .iter()
+                    .map(display)
+                    .collect::<Vec<_>>()
+                    .join(&display(&args[0])),
+            ),
+            val => return Err(format!("cannot join {val}")),
+        },
+        Func::Index => match (&args[0], &args[1]) {
+            (Value::Array(items), Value::Number(idx)) => idx
+                .as_u64()
+                .and_then(|idx| items.get(idx as usize))
+                .cloned()
+                .unwrap_or(Value::Null),
+            (Value::Object(map), Value::String(key)) => {
+                map.get(key).cloned().unwrap_or(Value::Null)
+            }
+            (val, key) => return Err(format!("cannot index {val} with {key}")),
+        },
+    };
+
+    Ok(val)
+}
+
+fn lookup(val: &Value, path: &[String]) -> Value {
+    path.iter()
+        .try_fold(val, |val, key| match val {
+            Value::Object(map) => map.get(key),
+            _ => None,
+        })
+        .cloned()
+        .unwrap_or(Value::Null)
+}
+
+fn is_truthy(val: &Value) -> bool {
+    match val {
+        Value::Null => false,
+        Value::Bool(b) => *b,
+        Value::Number(n) => n.as_f64().is_some_and(|n| n != 0.0),
+        Value::String(s) => !s.is_empty(),
+        Value::Array(items) => !items.is_empty(),
+        Value::Object(map) => !map.is_empty(),
+    }
+}
+
+/// strings are rendered as is, lists and objects as json
+fn display(val: &Value) -> String {
+    match val {
+        Value::Null => String::new(),
+        Value::String(s) => s.clone(),
+        val => val.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Value};
+
+    use super::Template;
+
+    fn render(template: &str, val: Value) -> String {
+        Template::parse(template)
+            .unwrap_or_else(|e| panic!("failed parsing `{template}`: {e}"))
+            .render(&val)
+            .unwrap()
+    }
+
+    fn parse_err(template: &str) -> String {
+        match Template::parse(template) {
+            Ok(parsed) => panic!("`{template}` parsed as {parsed:?}"),
+            Err(e) => e,
+        }
+    }
+
+    #[test]
+    fn renders_fields() {
+        let val = json!({ "name": "pets", "mock_server": { "url": "https://mock" }, "n": 3 });
+
+        assert_eq!(render("{{.name}}@{{ .n }}", val.clone()), "pets@3");
+        assert_eq!(render("{{.mock_server.url}}", val.clone()), "https://mock");
+        assert_eq!(render("{{.mock_server}}", val), r#"{"url":"https://mock"}"#);
+        assert_eq!(render("{{.}}", json!("plain")), "plain");
+        assert_eq!(render(r"{{.}}\n\t", json!(1)), "1\n\t");
+    }
+
+    #[test]
+    fn renders_missing_and_null_fields_as_empty() {
+        let val = json!({ "name": null, "api": { "id": "a1" } });
+
+        assert_eq!(render("[{{.name}}]", val.clone()), "[]");
+        assert_eq!(render("[{{.missing}}]", val.clone()), "[]");
+        assert_eq!(render("[{{.api.id.nested}}]", val.clone()), "[]");
+        assert_eq!(render("[{{.missing | len}}]", val), "[0]");
+    }
+
+    #[test]
+    fn pipes_values_into_functions() {
+        let val = json!({ "name": "Pets", "tags": ["a", "b"], "meta": { "k": "v" } });
+
+        assert_eq!(render("{{.name | upper}}", val.clone()), "PETS");
+        assert_eq!(render("{{.name | lower | upper}}", val.clone()), "PETS");
+        assert_eq!(render("{{.tags | len}}", val.clone()), "2");
+        assert_eq!(render(r#"{{.tags | join ", "}}"#, val.clone()), "a, b");
+        assert_eq!(render(r#"{{join "|" .tags}}"#, val.clone()), "a|b");
+        assert_eq!(render("{{index .tags 1}}", val.clone()), "b");
+        assert_eq!(render(r#"{{index .meta "k"}}"#, val.clone()), "v");
+        assert_eq!(render("{{.name | json}}", val), r#""Pets""#);
+    }
+
+    #[test]
+    fn renders_if_and_else() {
+        let template = "{{if .ok}}yes{{else}}no{{end}}";
+
+        assert_eq!(render(template, json!({ "ok": true })), "yes");
+        assert_eq!(render(template, json!({ "ok": false })), "no");
+        assert_eq!(render(template, json!({ "ok": "" })), "no");
+        assert_eq!(render(template, json!({})), "no");
+        assert_eq!(
+            render("{{if .items}}some{{end}}", json!({ "items": [] })),
+            ""
+        );
+    }
+
+    #[test]
+    fn chains_else_if() {
+        let template = "{{if .a}}A{{else if .b}}B{{else}}C{{end}}";
+
+        assert_eq!(render(template, json!({ "a": true, "b": true })), "A");
+        assert_eq!(render(template, json!({ "a": false, "b": true })), "B");
+        assert_eq!(render(template, json!({ "a": false, "b": false })), "C");
+        assert_eq!(
+            render("{{if .a}}A{{else if .b}}B{{end}}", json!({ "b": false })),
+            ""
+        );
+        assert_eq!(
+            render(
+                "{{if .a}}A{{else if .b}}B{{else if .c}}C{{end}}!",
+                json!({ "c": 1 })
+            ),
+            "C!"
+        );
+    }
+
+    #[test]
+    fn ranges_over_lists_and_objects() {
+        let val = json!({ "items": [{ "n": 1 }, { "n": 2 }], "map": { "a": "x", "b": "y" } });
+
+        assert_eq!(
+            render("{{range .items}}{{.n}};{{end}}", val.clone()),
+            "1;2;"
+        );
+        assert_eq!(render("{{range .map}}{{.}}{{end}}", val.clone()), "xy");
+        assert_eq!(
+            render("{{range .missing}}{{.}}{{else}}none{{end}}", val.clone()),
+            "none"
+        );
+    }
+
+    #[test]
+    fn fails_rendering_invalid_values() {
+        let template = Template::parse("{{range .n}}{{end}}").unwrap();
+        assert!(template.render(&json!({ "n": 1 })).is_err());
+
+        let template = Template::parse("{{.n | upper | len | join}}").unwrap();
+        assert!(template.render(&json!({ "n": "x" })).is_err());
+    }
+
+    #[test]
+    fn rejects_invalid_templates() {
+        assert!(parse_err("{{.name").contains("unclosed action"));
+        assert!(parse_err("{{if .a}}A").contains("missing {{end}}"));
+        assert!(parse_err("{{if .a}}A{{else}}B").contains("missing {{end}}"));
+        assert!(parse_err("{{if .a}}A{{else}}B{{else}}C{{end}}").contains("after {{else}}"));
+        assert!(parse_err("A{{end}}").contains("unexpected {{end}}"));
+        assert!(parse_err("{{else}}").contains("unexpected {{else}}"));
+        assert!(parse_err("{{if .a}}A{{end foo}}").contains("unexpected `foo` in {{end}}"));
+        assert!(parse_err("{{if .a}}A{{else .b}}B{{end}}").contains("unexpected `.b` in {{else}}"));
+        assert!(parse_err("{{if}}A{{end}}").contains("empty action"));
+        assert!(parse_err("{{.a | shout}}").contains("unknown function or argument `shout`"));
+        assert!(parse_err("{{.a .b}}").contains("unexpected arguments"));
+    }
+}
@@ -0,0 +1,241 @@
+use std::{
+    future::Future,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use log::{debug, error, warn};
+use sideko_rest_api::{
+    models::{CliUpdate, CliUpdateSeverityEnum},
+    resources::cli::CheckUpdatesRequest,
+};
+use tokio::task::JoinHandle;
+
+use crate::result::{CliError, CliResult, ErrorCode};
+
+use super::config::{self, ConfigKey, DEFAULT_UPDATE_CHECK_TTL};
+
+/// how long a finished command waits for a pending update check before giving up on it
+const PENDING_CHECK_GRACE: Duration = Duration::from_secs(1);
+
+/// update check started alongside a command
+pub(crate) enum UpdateCheck {
+    /// disabled via `--offline` or `SIDEKO_NO_UPDATE_CHECK`
+    Disabled,
+    /// fresh result read from the on-disk cache
+    Cached(Vec<CliUpdate>),
+    /// request running in the background, `enforce` tells whether a required update
+    /// fails the command
+    Pending {
+        handle: JoinHandle<CliResult<Vec<CliUpdate>>>,
+        enforce: bool,
+    },
+}
+
+impl UpdateCheck {
+    /// starts the update check, using the cached result if it has not expired yet
+    ///
+    /// returns an Err if required updates are enforced and the cached result requires one
+    pub(crate) fn start(enforce: bool) -> CliResult<Self> {
+        if is_disabled() {
+            debug!("update check is disabled");
+            return Ok(Self::Disabled);
+        }
+
+        if let Some(updates) = UpdateCache::read_fresh() {
+            debug!("using cached update check result");
+            if enforce {
+                return Ok(Self::Cached(enforce_required(updates)?));
+            }
+            return Ok(Self::Cached(updates));
+        }
+
+        Ok(Self::Pending {
+            handle: tokio::spawn(fetch_updates()),
+            enforce,
+        })
+    }
+
+    /// runs the command to completion, a running command is never cancelled
+    ///
+    /// a cached required update is enforced before the command starts (see `start`), a
+    /// pending check that finds one fails the command once it completed
+    ///
+    /// returns the command result along with the updates to log afterwards
+    pub(crate) async fn run<F>(self, cmd: F) -> (CliResult<()>, Vec<CliUpdate>)
+    where
+        F: Future<Output = CliResult<()>>,
+    {
+        let (mut handle, enforce) = match self {
+            Self::Disabled => return (cmd.await, vec![]),
+            Self::Cached(updates) => return (cmd.await, updates),
+            Self::Pending { handle, enforce } => (handle, enforce),
+        };
+
+        // the check keeps running in the background meanwhile
+        let cmd_res = cmd.await;
+        let updates = match tokio::time::timeout(PENDING_CHECK_GRACE, &mut handle).await {
+            Ok(checked) => checked_updates(checked),
+            Err(_) => {
+                debug!("update check did not finish in time, skipping it");
+                handle.abort();
+                vec![]
+            }
+        };
+        if !enforce {
+            return (cmd_res, updates);
+        }
+
+        match enforce_required(updates) {
+            Ok(updates) => (cmd_res, updates),
+            // the error of a failed command is more relevant, the update was logged
+            Err(e) => (cmd_res.and(Err(e)), vec![]),
+        }
+    }
+}
+
+fn is_disabled() -> bool {
+    ConfigKey::NoUpdateCheck
+        .get_env()
+        .is_some_and(|val| config::parse_bool(&val).unwrap_or(true))
+}
+
+fn cli_version() -> String {
+    env!("CARGO_PKG_VERSION").to_string()
+}
+
+/// Uses the sideko api to check for cli notices/update requirements, caching the
+/// result on success
+async fn fetch_updates() -> CliResult<Vec<CliUpdate>> {
+    let cli_version = cli_version();
+    debug!("checking for updates (cli version: {cli_version})...");
+
+    let mut client = super::get_base_sideko_client();
+    let updates = client
+        .cli()
+        .check_updates(CheckUpdatesRequest { cli_version })
+        .await?;
+
+    UpdateCache::new(updates.clone()).write();
+
+    Ok(updates)
+}
+
+/// unwraps the result of the background check, a failed check only warns
+fn checked_updates(
+    checked: Result<CliResult<Vec<CliUpdate>>, tokio::task::JoinError>,
+) -> Vec<CliUpdate> {
+    let err = match checked {
+        Ok(Ok(updates)) => return updates,
+        Ok(Err(e)) => format!("{e:?}"),
+        Err(e) => format!("{e:?}"),
+    };
+    warn!(
+        "failed checking for cli updates, disable the check with --offline or ${}",
+        ConfigKey::NoUpdateCheck
+    );
+    debug!("update check error: {err}");
+
+    vec![]
+}
+
+/// logs required updates and returns an Err if there are any, otherwise returns the
+/// remaining updates to be logged at the end of the command
+fn enforce_required(updates: Vec<CliUpdate>) -> CliResult<Vec<CliUpdate>> {
+    let mut early_exit = false;
+    for update in &updates {
+        if matches!(&update.severity, CliUpdateSeverityEnum::Required) {
+            error!("{}", update.message);
+            early_exit = true;
+        }
+    }
+
+    if early_exit {
+        return Err(
+            CliError::general("must update cli to continue").with_code(ErrorCode::UpdateRequired)
+        );
+    }
+
+    Ok(updates
+        .into_iter()
+        .filter(|u| !matches!(&u.severity, CliUpdateSeverityEnum::Required))
+        .collect())
+}
+
+/// result of the last successful update check, only valid for the same cli version
+/// and api base url
+#[derive(serde::Serialize, serde::Deserialize)]
+struct UpdateCache {
+    cli_version: String,
+    base_url: String,
+    /// unix timestamp (seconds) of the check
+    checked_at: u64,
+    updates: Vec<CliUpdate>,
+}
+
+impl UpdateCache {
+    fn new(updates: Vec<CliUpdate>) -> Self {
+        Self {
+            cli_version: cli_version(),
+            base_url: config::get_base_url(),
+            checked_at: now_secs(),
+            updates,
+        }
+    }
+
+    /// reads the cached updates if they were checked within the configured ttl
+    fn read_fresh() -> Option<Vec<CliUpdate>> {
+        let ttl = ttl_secs();
+        if ttl == 0 {
+            return None;
+        }
+
+        let path = config::get_update_cache_path().ok()?;
+        let content = std::fs::read_to_string(&path).ok()?;
+        let cache: UpdateCache = match serde_json::from_str(&content) {
+            Ok(cache) => cache,
+            Err(e) => {
+                debug!("ignoring invalid update check cache {path}: {e}");
+                return None;
+            }
+        };
+
+        let fresh = cache.cli_version == cli_version()
+            && cache.base_url == config::get_base_url()
+            && now_secs().saturating_sub(cache.checked_at) < ttl;
+
+        fresh.then_some(cache.updates)
+    }
+
+    /// failing to cache the result is not worth interrupting the command for
+    fn write(&self) {
+        let written = config::get_update_cache_path().and_then(|path| {
+            let content = serde_json::to_string(self)
+                .map_err(|e| CliError::general_debug("failed serializing update check cache", e))?;
+            std::fs::write(&path, content)
+                .map_err(|e| CliError::io_custom(format!("failed writing {path}"), e))
+        });
+        if let Err(e) = written {
+            debug!("failed caching update check result: {e:?}");
+        }
+    }
+}
+
+fn ttl_secs() -> u64 {
+    match ConfigKey::UpdateCheckTtl.get_env() {
+        Some(val) => val.parse().unwrap_or_else(|_| {
+            warn!(
+                "invalid ${} `{val}`, using the default of {DEFAULT_UPDATE_CHECK_TTL} seconds",
+                ConfigKey::UpdateCheckTtl
+            );
+            DEFAULT_UPDATE_CHECK_TTL
+        }),
+        None => DEFAULT_UPDATE_CHECK_TTL,
+    }
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}